@@ -1,4 +1,4 @@
-use super::parse::CallDef;
+use super::parse::{CallDef, OriginArg, ReceiverArg};
 use quote::quote;
 
 /// See the `fn call` docs at the `lib.rs` of this crate for a high level definition.
@@ -8,9 +8,46 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 	// This is a vector of all the callable function names.
 	let fn_name = methods.iter().map(|method| &method.name).collect::<Vec<_>>();
 
+	// This is a vector of expressions which turn the `origin` of the call into the second argument
+	// of each of the functions in `fn_name`: either the signed `caller`, or the origin itself.
+	let origin_arg = methods
+		.iter()
+		.map(|method| match method.origin {
+			OriginArg::Caller => quote! { crate::support::ensure_signed(origin)? },
+			OriginArg::Origin => quote! { origin },
+		})
+		.collect::<Vec<_>>();
+
+	// This is a vector of expressions which call each of the functions in `fn_name`, either on the
+	// pallet itself, or as an associated function taking the whole runtime.
+	let fn_call = methods
+		.iter()
+		.map(|method| {
+			let name = &method.name;
+			match method.receiver {
+				ReceiverArg::Pallet => quote! {
+					AsMut::<#pallet_struct<T>>::as_mut(runtime).#name
+				},
+				ReceiverArg::Runtime => quote! {
+					#pallet_struct::<T>::#name
+				},
+			}
+		})
+		.collect::<Vec<_>>();
+
+	// This is a vector of the first arguments passed to each of the functions in `fn_name`, which
+	// is the runtime for associated functions, and nothing for functions taking `self`.
+	let runtime_arg = methods
+		.iter()
+		.map(|method| match method.receiver {
+			ReceiverArg::Pallet => quote! {},
+			ReceiverArg::Runtime => quote! { runtime, },
+		})
+		.collect::<Vec<_>>();
+
 	// This is a nested vector of all the arguments for each of the functions in `fn_name`. It does
-	// not include the `self` or `caller: T::AccountId` parameter, which we always assume are the
-	// first two parameters to these calls.
+	// not include the `self` / `runtime: &mut T` or `caller: T::AccountId` / `origin: OriginFor<T>`
+	// parameter, which we always assume are the first two parameters to these calls.
 	let args_name = methods
 		.iter()
 		.map(|method| method.args.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>())
@@ -23,9 +60,16 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		.map(|method| method.args.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>())
 		.collect::<Vec<_>>();
 
-	// This quote block creates an `enum Call` which contains all the calls exposed by our pallet,
-	// and the `Dispatch` trait logic to route a `caller` to access those functions.
-	let dispatch_impl = quote! {
+	// This is a flat vector of the types of all the arguments of all the functions, used to bound
+	// the traits implemented for the `Call` enum.
+	let all_args_type = args_type.iter().flatten().collect::<Vec<_>>();
+
+	// This quote block creates an `enum Call` which contains all the calls exposed by our pallet.
+	//
+	// We implement `Clone`, `Debug` and `Hash` by hand rather than deriving them, since deriving
+	// them would require `T` itself to implement these traits, when we only need the arguments to.
+	// `Hash` allows calls to be identified by their hash, like in a multisig.
+	let call_enum = quote! {
 		// The callable functions exposed by this pallet.
 		//
 		// The parsed function names will be `snake_case`, and that will show up in the enum.
@@ -36,19 +80,91 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 			)*
 		}
 
-		// Dispatch logic at the pallet level, mapping each of the items in the `Call` enum to the
-		// appropriate function call with all arguments, including the `caller`.
-		impl<T: Config> crate::support::Dispatch for #pallet_struct<T> {
-			type Caller = T::AccountId;
-			type Call = Call<T>;
+		impl<T: Config> Clone for Call<T>
+		where
+			#( #all_args_type: Clone, )*
+		{
+			fn clone(&self) -> Self {
+				match self {
+					#(
+						Call::#fn_name { #( #args_name ),* } => Call::#fn_name {
+							#( #args_name: #args_name.clone() ),*
+						},
+					)*
+				}
+			}
+		}
+
+		impl<T: Config> core::fmt::Debug for Call<T>
+		where
+			#( #all_args_type: core::fmt::Debug, )*
+		{
+			fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+				match self {
+					#(
+						Call::#fn_name { #( #args_name ),* } => f
+							.debug_struct(stringify!(#fn_name))
+							#( .field(stringify!(#args_name), #args_name) )*
+							.finish(),
+					)*
+				}
+			}
+		}
+
+		impl<T: Config> Call<T> {
+			// The name of the function this call is routed to.
+			pub fn call_name(&self) -> &'static str {
+				match self {
+					#(
+						Call::#fn_name { .. } => stringify!(#fn_name),
+					)*
+				}
+			}
+		}
+
+		impl<T: Config> core::hash::Hash for Call<T>
+		where
+			#( #all_args_type: core::hash::Hash, )*
+		{
+			fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+				match self {
+					#(
+						Call::#fn_name { #( #args_name ),* } => {
+							core::hash::Hash::hash(stringify!(#fn_name), state);
+							#( core::hash::Hash::hash(#args_name, state); )*
+						},
+					)*
+				}
+			}
+		}
+	};
 
-			fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> crate::support::DispatchResult {
-				match call {
+	// This quote block creates the dispatch logic to route an `origin` to access the functions
+	// exposed by our pallet.
+	let dispatch_impl = quote! {
+		impl<T: Config> Call<T> {
+			// Dispatch logic at the pallet level, mapping each of the items in the `Call` enum to
+			// the appropriate function call with all arguments, including the `caller` or
+			// `origin`.
+			//
+			// Calls are dispatched on the whole `runtime` including this pallet, so that calls
+			// taking `runtime: &mut T` can access other pallets too.
+			pub fn dispatch(
+				self,
+				runtime: &mut T,
+				origin: crate::support::RawOrigin<T::AccountId>,
+			) -> crate::support::DispatchResult
+			where
+				T: AsMut<#pallet_struct<T>>,
+			{
+				match self {
 					#(
 						Call::#fn_name { #( #args_name ),* } => {
-							self.#fn_name(
-								// Note that we assume the first argument of every call is the `caller`.
-								caller,
+							#fn_call(
+								#runtime_arg
+								// Note that we assume the next argument of every call is the
+								// `caller` or the `origin`.
+								#origin_arg,
 								#( #args_name ),*
 							)?;
 						},
@@ -60,5 +176,8 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 	};
 
 	// Return the generated code.
-	dispatch_impl.into()
+	quote! {
+		#call_enum
+		#dispatch_impl
+	}
 }
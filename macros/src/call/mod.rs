@@ -6,12 +6,22 @@ pub fn call(
 	_attr: proc_macro::TokenStream,
 	item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-	// The final expanded code will be placed here.
-	// Since our macro only adds new code, our final product will contain all of our old code too,
-	// hence we clone `item`.
-	let mut finished = item.clone();
 	let item_mod = syn::parse_macro_input!(item as syn::Item);
 
+	// The final expanded code will be placed here.
+	// Our macro only adds new code, so our final product will contain all of our old code too,
+	// hence we clone `item_mod`. The only change is to the lints: calls which take another call,
+	// like scheduling or dispatching it later, must box it in their arguments, since the `Call`
+	// enum would otherwise contain itself. Clippy would rather these arguments were unboxed, so its
+	// `boxed_local` lint is allowed on all the callable functions.
+	let mut finished: proc_macro::TokenStream = match item_mod.clone() {
+		syn::Item::Impl(mut item_impl) => {
+			item_impl.attrs.push(syn::parse_quote!(#[allow(clippy::boxed_local)]));
+			quote::quote!(#item_impl).into()
+		},
+		item => quote::quote!(#item).into(),
+	};
+
 	// First we parse the call functions implemented for the pallet...
 	let generated: proc_macro::TokenStream = match parse::CallDef::try_from(item_mod.clone()) {
 		// ..then we generate our new code.
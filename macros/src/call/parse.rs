@@ -7,6 +7,25 @@ mod keyword {
 	syn::custom_keyword!(AccountId);
 }
 
+/// How a callable function accesses the state of the runtime, decided by its first argument.
+#[derive(Debug)]
+pub enum ReceiverArg {
+	/// `&mut self`: the call only needs access to the storage of its own pallet.
+	Pallet,
+	/// `runtime: &mut T`: the call needs access to the whole runtime, to use other pallets or
+	/// dispatch other calls.
+	Runtime,
+}
+
+/// How a callable function receives the origin of the call, decided by its second argument.
+#[derive(Debug)]
+pub enum OriginArg {
+	/// `caller: T::AccountId`: the call must be signed, and receives the signing account.
+	Caller,
+	/// `origin: OriginFor<T>`: the call receives the raw origin, and checks it itself.
+	Origin,
+}
+
 /// This object will collect all the information we need to keep while parsing the callable
 /// functions.
 #[derive(Debug)]
@@ -23,6 +42,10 @@ pub struct CallDef {
 pub struct CallVariantDef {
 	/// The function name.
 	pub name: syn::Ident,
+	/// How the function accesses the state of the runtime.
+	pub receiver: ReceiverArg,
+	/// How the function receives the origin of the call.
+	pub origin: OriginArg,
 	/// Information on args of the function: `(name, type)`.
 	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
 }
@@ -50,27 +73,34 @@ impl CallDef {
 				// Here is where we will store all the args for each callable functions.
 				let mut args = vec![];
 
-				// First argument should be some variant of `self`.
-				match method.sig.inputs.first() {
-					Some(syn::FnArg::Receiver(_)) => {},
+				// First argument should be some variant of `self`, or `runtime: &mut T`.
+				let receiver = match method.sig.inputs.first() {
+					Some(syn::FnArg::Receiver(_)) => ReceiverArg::Pallet,
+					Some(syn::FnArg::Typed(arg)) => {
+						check_runtime_arg(arg)?;
+						ReceiverArg::Runtime
+					},
 					_ => {
-						let msg = "Invalid call, first argument must be a variant of self";
+						let msg = "Invalid call, first argument must be a variant of self, or \
+							`runtime: &mut T`";
 						return Err(syn::Error::new(method.sig.span(), msg))
 					},
-				}
+				};
 
-				// The second argument should be the `caller: T::AccountId` argument.
-				match method.sig.inputs.iter().skip(1).next() {
+				// The second argument should be the `caller: T::AccountId` argument, or the
+				// `origin: OriginFor<T>` argument.
+				let origin = match method.sig.inputs.iter().nth(1) {
 					Some(syn::FnArg::Typed(arg)) => {
 						// Here we specifically check that this argument is as we expect for
-						// `caller: T::AccountId`.
-						check_caller_arg(arg)?;
+						// `caller: T::AccountId` or `origin: OriginFor<T>`.
+						check_origin_arg(arg)?
 					},
 					_ => {
-						let msg = "Invalid call, second argument should be `caller: T::AccountId`";
+						let msg = "Invalid call, second argument should be `caller: T::AccountId` \
+							or `origin: OriginFor<T>`";
 						return Err(syn::Error::new(method.sig.span(), msg))
 					},
-				}
+				};
 
 				let fn_name = method.sig.ident.clone();
 
@@ -96,7 +126,7 @@ impl CallDef {
 				}
 
 				// Store all the function name and the arg data for the function.
-				methods.push(CallVariantDef { name: fn_name, args });
+				methods.push(CallVariantDef { name: fn_name, receiver, origin, args });
 			}
 		}
 
@@ -105,6 +135,65 @@ impl CallDef {
 	}
 }
 
+/// Check runtime arg is exactly: `runtime: &mut T`.
+///
+/// This is kept strict to keep the code simple.
+pub fn check_runtime_arg(arg: &syn::PatType) -> syn::Result<()> {
+	let name_is_runtime = match &*arg.pat {
+		syn::Pat::Ident(ident) => ident.ident == "runtime",
+		_ => false,
+	};
+	let type_is_mut_t = arg.ty.to_token_stream().to_string() == "& mut T";
+
+	if !name_is_runtime || !type_is_mut_t {
+		let msg = "Invalid first parameter: expected a variant of self, or `runtime: &mut T`";
+		return Err(syn::Error::new(arg.span(), msg))
+	}
+
+	Ok(())
+}
+
+/// Check the origin arg is exactly `caller: T::AccountId` or `origin: OriginFor<T>`, and return
+/// which one it is.
+pub fn check_origin_arg(arg: &syn::PatType) -> syn::Result<OriginArg> {
+	// This checks the arg name is `origin` or `_origin`, in which case the type must be
+	// `OriginFor<T>`.
+	if let syn::Pat::Ident(ident) = &*arg.pat {
+		if &ident.ident == "origin" || &ident.ident == "_origin" {
+			check_raw_origin_arg(arg)?;
+			return Ok(OriginArg::Origin)
+		}
+	}
+
+	check_caller_arg(arg)?;
+	Ok(OriginArg::Caller)
+}
+
+/// Check origin arg is exactly: `origin: OriginFor<T>`, where `OriginFor` may be given with its
+/// full path.
+///
+/// This is kept strict to keep the code simple.
+pub fn check_raw_origin_arg(arg: &syn::PatType) -> syn::Result<()> {
+	let is_origin_for = match &*arg.ty {
+		syn::Type::Path(tp) => tp.path.segments.last().is_some_and(|segment| {
+			let generic_is_t = match &segment.arguments {
+				syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 =>
+					args.args.first().unwrap().to_token_stream().to_string() == "T",
+				_ => false,
+			};
+			segment.ident == "OriginFor" && generic_is_t
+		}),
+		_ => false,
+	};
+
+	if !is_origin_for {
+		let msg = "Invalid type for second parameter: expected `origin: OriginFor<T>`";
+		return Err(syn::Error::new(arg.ty.span(), msg))
+	}
+
+	Ok(())
+}
+
 /// Check caller arg is exactly: `caller: T::AccountId`.
 ///
 /// This is kept strict to keep the code simple.
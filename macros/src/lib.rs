@@ -1,6 +1,25 @@
 mod call;
 mod runtime;
 
+/// Expand the callable functions of a pallet.
+///
+/// This generates an `enum Call` with one variant per function, and a `fn dispatch()` on it which
+/// routes the call to the appropriate function of the pallet, given the whole runtime. The `Call`
+/// enum implements `Clone`, `Debug` and `Hash` whenever the arguments of all the functions do.
+///
+/// The first argument of each function is either:
+/// - `&mut self` - for calls which only need the storage of their own pallet.
+/// - `runtime: &mut T` - for calls which need other pallets, through the `AsRef` and `AsMut`
+///   implementations of the runtime, or which dispatch other calls.
+///
+/// The second argument of each function is either:
+/// - `caller: T::AccountId` - for calls which must be signed. Other origins are rejected.
+/// - `origin: OriginFor<T>` - for calls which check the origin themselves, like inherents which
+///   expect no origin, or privileged calls which expect the root origin.
+///
+/// A function which takes another call as an argument must take it as a `Box<CallOf<T>>`, since
+/// the `Call` enum would otherwise contain itself. The `clippy::boxed_local` lint is allowed on the
+/// functions for this reason.
 #[proc_macro_attribute]
 pub fn call(
 	attr: proc_macro::TokenStream,
@@ -16,14 +35,29 @@ pub fn call(
 ///   included in the runtime.
 /// - `fn execute_block()` - which handles basic logic for executing a block of extrinsics. It does
 ///   basic actions like incrementing the block number and checking the block to be executed has a
-///   valid block number.
+///   valid block number. It also checks that every unsigned extrinsic is an inherent, that the
+///   inherents come first, and that each appears exactly once. The whole block is executed as a
+///   transaction, so the runtime must be `Clone`: an invalid block changes nothing.
+/// - `fn build_block()` - which builds the next block from the signed extrinsics to include,
+///   placing the inherents created from the block author's `InherentData` at its start.
+///
+/// Pallets which provide an inherent must be marked with `#[inherent]` in the `Runtime` struct,
+/// and implement `support::ProvideInherent`. Pallets which run logic at the start or end of every
+/// block must be marked with `#[hooks]`, and implement `support::Hooks`. Pallets which emit events
+/// must be marked with `#[event]`, and implement `support::PendingEvents`: their events are
+/// collected into an `enum RuntimeEvent`, and deposited in the system pallet.
+///
+/// It also implements `AsRef` and `AsMut` for every pallet, including system, so that pallets can
+/// access each other from the runtime.
 ///
 /// This also generates code needed for dispatching calls to the pallets:
 /// - Note: For simplicity, we assume that the system pallet is not callable.
 /// - `enum RuntimeCall` - an "outer"-enum representing the accumulation of all possible calls to
-///   all pallets. The system pallet is not included.
+///   all pallets. The system pallet is not included. It can be hashed, so that pallets like
+///   multisig can identify calls by their hash, and implements `support::GetCallMetadata`, so that
+///   pallets like proxy can filter calls by the pallet and function they are routed to.
 /// - implements the trait `support::Dispatch` to dispatch calls to the appropriate pallet. Basic
-///   logic like incrementing the nonce of the user is included in the generated code. The system
+///   logic like incrementing the nonce of the user is included in `execute_block`. The system
 ///   pallet is not included.
 #[proc_macro_attribute]
 pub fn runtime(
@@ -6,9 +6,37 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 	let RuntimeDef { runtime_struct, pallets } = def;
 
 	// This is a vector of all the pallet names, not including system.
-	let pallet_names = pallets.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+	let pallet_names = pallets.iter().map(|pallet| pallet.name.clone()).collect::<Vec<_>>();
 	// This is a vector of all the pallet types, not including system.
-	let pallet_types = pallets.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>();
+	let pallet_types = pallets.iter().map(|pallet| pallet.ty.clone()).collect::<Vec<_>>();
+
+	// These are the names and types of the pallets which provide an inherent.
+	let inherent_pallets = pallets.iter().filter(|pallet| pallet.inherent).collect::<Vec<_>>();
+	let inherent_names = inherent_pallets.iter().map(|pallet| &pallet.name).collect::<Vec<_>>();
+	let inherent_types = inherent_pallets.iter().map(|pallet| &pallet.ty).collect::<Vec<_>>();
+	// The index of each inherent pallet, used to count how many times its inherent appears.
+	let inherent_indices = (0..inherent_pallets.len()).collect::<Vec<_>>();
+	let inherent_count = inherent_pallets.len();
+
+	// These are the types of the pallets which implement hooks.
+	let hooks_types =
+		pallets.iter().filter(|pallet| pallet.hooks).map(|pallet| &pallet.ty).collect::<Vec<_>>();
+
+	// These are the names and types of the pallets which emit events.
+	let event_pallets = pallets.iter().filter(|pallet| pallet.event).collect::<Vec<_>>();
+	let event_names = event_pallets.iter().map(|pallet| &pallet.name).collect::<Vec<_>>();
+	let event_types = event_pallets.iter().map(|pallet| &pallet.ty).collect::<Vec<_>>();
+
+	// The concrete block and extrinsic types used by this runtime.
+	let extrinsic_type = quote! {
+		crate::support::Extrinsic<<#runtime_struct as crate::system::Config>::AccountId, RuntimeCall>
+	};
+	let block_type = quote! {
+		crate::support::Block<
+			crate::support::Header<<#runtime_struct as crate::system::Config>::BlockNumber>,
+			#extrinsic_type,
+		>
+	};
 
 	// This quote block implements functions on the `Runtime` struct.
 	let runtime_impl = quote! {
@@ -17,33 +45,164 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 			fn new() -> Self {
 				Self {
 					// Since system is not included in the list of pallets, we manually add it here.
-					system: <system::Pallet::<Self>>::new(),
+					system: <crate::system::Pallet::<Self>>::new(),
 					#(
 						#pallet_names: <#pallet_types>::new()
 					),*
 				}
 			}
 
-			// Execute a block of extrinsics. Increments the block number.
-			fn execute_block(&mut self, block: types::Block) -> crate::support::DispatchResult {
+			// Build the next block on top of the current state, from the `inherent_data` provided
+			// by the block author and the signed `extrinsics` to include.
+			//
+			// The inherents of all pallets providing one are created and placed at the start of the
+			// block, before the signed extrinsics.
+			fn build_block(
+				&self,
+				inherent_data: &crate::support::InherentData,
+				extrinsics: Vec<#extrinsic_type>,
+			) -> #block_type {
+				let mut block_number = self.system.block_number();
+				block_number +=
+					<<#runtime_struct as crate::system::Config>::BlockNumber as ::num::One>::one();
+
+				let mut all_extrinsics = Vec::new();
+				#(
+					if let Some(call) = crate::support::ProvideInherent::create_inherent(
+						&self.#inherent_names,
+						inherent_data,
+					) {
+						all_extrinsics.push(crate::support::Extrinsic::new_inherent(
+							RuntimeCall::#inherent_names(call),
+						));
+					}
+				)*
+				all_extrinsics.extend(extrinsics);
+
+				crate::support::Block {
+					header: crate::support::Header { block_number },
+					extrinsics: all_extrinsics,
+				}
+			}
+
+			// Check that the inherents of a block are valid: every unsigned extrinsic must be an
+			// inherent, all inherents must come before the signed extrinsics, and each pallet
+			// providing an inherent must have exactly one in the block.
+			#[allow(unused_mut)]
+			fn check_inherents(block: &#block_type) -> crate::support::DispatchResult {
+				let mut signed_seen = false;
+				let mut inherents_seen = [0usize; #inherent_count];
+				for extrinsic in block.extrinsics.iter() {
+					if extrinsic.is_signed() {
+						signed_seen = true;
+						continue
+					}
+					if signed_seen {
+						return Err("inherents must come before all signed extrinsics")
+					}
+					match &extrinsic.call {
+						#(
+							RuntimeCall::#inherent_names(call)
+								if <#inherent_types as crate::support::ProvideInherent>::is_inherent(call) =>
+								inherents_seen[#inherent_indices] += 1,
+						)*
+						_ => return Err("unsigned extrinsic is not an inherent"),
+					}
+				}
+				if inherents_seen.iter().any(|seen| *seen != 1) {
+					return Err("each inherent must appear exactly once")
+				}
+				Ok(())
+			}
+
+			// Collect the events emitted by the pallets into the system pallet.
+			fn collect_events(&mut self) {
+				#(
+					for event in crate::support::PendingEvents::take_events(&mut self.#event_names) {
+						self.system.deposit_event(RuntimeEvent::#event_names(event));
+					}
+				)*
+			}
+
+			// Execute a block of extrinsics. Increments the block number, and removes the events of
+			// the previous block. If the block is invalid, none of its changes are kept.
+			//
+			// The inherents are dispatched first, with no origin, so that the hooks see their
+			// effects, like the time of the block. The block is invalid if any of them fails. The
+			// `on_initialize` hooks of the pallets run next, then the signed extrinsics, and the
+			// `on_finalize` hooks after all of them. Signed extrinsics increment the nonce of their
+			// caller, and their errors are only logged. The system pallet knows the index of the
+			// extrinsic being executed.
+			fn execute_block(&mut self, block: #block_type) -> crate::support::DispatchResult {
+				Self::check_inherents(&block)?;
+				crate::support::with_transaction(self, |runtime| runtime.apply_block(block))
+			}
+
+			// Apply the changes of a block whose inherents were checked, see `execute_block`.
+			fn apply_block(&mut self, block: #block_type) -> crate::support::DispatchResult {
 				self.system.inc_block_number();
 				if block.header.block_number != self.system.block_number() {
 					return Err(&"block number does not match what is expected")
 				}
-				for (i, support::Extrinsic { caller, call }) in block.extrinsics.into_iter().enumerate() {
-					self.system.inc_nonce(&caller);
-					let _res = self.dispatch(caller, call).map_err(|e| {
+				self.system.reset_events();
+				// The inherents come before all signed extrinsics, as checked above.
+				let mut extrinsics = block.extrinsics.into_iter().enumerate().peekable();
+				while let Some((i, extrinsic)) =
+					extrinsics.next_if(|(_, extrinsic)| !extrinsic.is_signed())
+				{
+					self.system.set_extrinsic_index(Some(i as u32));
+					crate::support::Dispatch::dispatch(
+						self,
+						crate::support::RawOrigin::None,
+						extrinsic.call,
+					)?;
+				}
+				self.system.set_extrinsic_index(None);
+				#(
+					<#hooks_types as crate::support::Hooks<Self, _>>::on_initialize(
+						self,
+						block.header.block_number,
+					);
+				)*
+				self.collect_events();
+				for (i, crate::support::Extrinsic { caller, call }) in extrinsics {
+					let Some(caller) = caller else { continue };
+					self.system.set_extrinsic_index(Some(i as u32));
+					self.system.inc_nonce(caller.clone());
+					let _res = crate::support::Dispatch::dispatch(
+						self,
+						crate::support::RawOrigin::Signed(caller),
+						call,
+					).map_err(|e| {
 						eprintln!(
 							"Extrinsic Error\n\tBlock Number: {}\n\tExtrinsic Number: {}\n\tError: {}",
 							block.header.block_number, i, e
 						)
 					});
 				}
+				self.system.set_extrinsic_index(None);
+				#(
+					<#hooks_types as crate::support::Hooks<Self, _>>::on_finalize(
+						self,
+						block.header.block_number,
+					);
+				)*
+				self.collect_events();
 				Ok(())
 			}
 		}
 	};
 
+	// This quote block implements the `RuntimeEvent` enum, which is an accumulation of the events
+	// emitted by each pallet.
+	let event_impl = quote! {
+		#[allow(non_camel_case_types)]
+		#[derive(Debug, Clone)]
+		pub enum RuntimeEvent {
+			#( #event_names(<#event_types as crate::support::PendingEvents>::Event) ),*
+		}
+	};
+
 	// This quote block implements the `RuntimeCall` enum and implements the `Dispatch` trait.
 	let dispatch_impl = quote! {
 		// These are all the calls which are exposed to the world.
@@ -51,41 +210,95 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 		//
 		// The parsed function names will be `snake_case`, and that will show up in the enum.
 		#[allow(non_camel_case_types)]
+		#[derive(Debug, Clone, Hash)]
 		pub enum RuntimeCall {
 			#( #pallet_names(#pallet_names::Call<#runtime_struct>) ),*
 		}
 
 		impl crate::support::Dispatch for #runtime_struct {
-			type Caller = <Runtime as system::Config>::AccountId;
+			type Caller = crate::support::RawOrigin<<#runtime_struct as crate::system::Config>::AccountId>;
 			type Call = RuntimeCall;
-			// Dispatch a call on behalf of a caller. Increments the caller's nonce.
+			// Dispatch a call on behalf of an origin. The events emitted by the call are collected
+			// once it is done, even if it fails.
 			//
 			// Dispatch allows us to identify which underlying pallet call we want to execute.
 			// Note that we extract the `caller` from the extrinsic, and use that information
 			// to determine who we are executing the call on behalf of.
 			fn dispatch(
 				&mut self,
-				caller: Self::Caller,
+				origin: Self::Caller,
 				runtime_call: Self::Call,
 			) -> crate::support::DispatchResult {
 				// This match statement will allow us to correctly route `RuntimeCall`s
 				// to the appropriate pallet level call.
-				match runtime_call {
+				let result = match runtime_call {
 					#(
-						RuntimeCall::#pallet_names(call) => {
-							self.#pallet_names.dispatch(caller, call)?;
-						}
-					),*
+						RuntimeCall::#pallet_names(call) => call.dispatch(self, origin),
+					)*
+				};
+				self.collect_events();
+				result
+			}
+		}
+
+		// The names of the pallet and function each call is routed to, which allow calls to be
+		// filtered, like the calls a proxy is allowed to make.
+		impl crate::support::GetCallMetadata for RuntimeCall {
+			fn pallet_name(&self) -> &'static str {
+				match self {
+					#(
+						RuntimeCall::#pallet_names(_) => stringify!(#pallet_names),
+					)*
+				}
+			}
+
+			fn call_name(&self) -> &'static str {
+				match self {
+					#(
+						RuntimeCall::#pallet_names(call) => call.call_name(),
+					)*
 				}
-				Ok(())
 			}
 		}
 	};
 
+	// This quote block gives access to each pallet, including system, from the runtime. This is
+	// how pallets access each other: calls and hooks taking the whole runtime can get any pallet
+	// `P` the runtime implements `AsRef<P>` and `AsMut<P>` for.
+	let access_impl = quote! {
+		impl AsRef<crate::system::Pallet<#runtime_struct>> for #runtime_struct {
+			fn as_ref(&self) -> &crate::system::Pallet<#runtime_struct> {
+				&self.system
+			}
+		}
+
+		impl AsMut<crate::system::Pallet<#runtime_struct>> for #runtime_struct {
+			fn as_mut(&mut self) -> &mut crate::system::Pallet<#runtime_struct> {
+				&mut self.system
+			}
+		}
+
+		#(
+			impl AsRef<#pallet_types> for #runtime_struct {
+				fn as_ref(&self) -> &#pallet_types {
+					&self.#pallet_names
+				}
+			}
+
+			impl AsMut<#pallet_types> for #runtime_struct {
+				fn as_mut(&mut self) -> &mut #pallet_types {
+					&mut self.#pallet_names
+				}
+			}
+		)*
+	};
+
 	// We combine and return all the generated code.
 	quote! {
+		#event_impl
 		#dispatch_impl
 		#runtime_impl
+		#access_impl
 	}
 	.into()
 }
@@ -6,11 +6,14 @@ pub fn runtime(
 	_attr: proc_macro::TokenStream,
 	item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+	let item_mod = syn::parse_macro_input!(item as syn::Item);
+
 	// The final expanded code will be placed here.
 	// Since our macro only adds new code, our final product will contain all of our old code too,
-	// hence we clone `item`.
-	let mut finished = item.clone();
-	let item_mod = syn::parse_macro_input!(item as syn::Item);
+	// except for the attributes used to mark pallets, which we remove.
+	let mut original = item_mod.clone();
+	parse::remove_pallet_attrs(&mut original);
+	let mut finished: proc_macro::TokenStream = quote::quote!(#original).into();
 
 	// First we parse the `Runtime` struct...
 	let generated: proc_macro::TokenStream = match parse::RuntimeDef::try_from(item_mod.clone()) {
@@ -1,5 +1,14 @@
+use quote::ToTokens;
 use syn::spanned::Spanned;
 
+/// The name of the attribute used to mark pallets which provide an inherent in the `Runtime`
+/// struct.
+const INHERENT_ATTR: &str = "inherent";
+/// The name of the attribute used to mark pallets which implement hooks in the `Runtime` struct.
+const HOOKS_ATTR: &str = "hooks";
+/// The name of the attribute used to mark pallets which emit events in the `Runtime` struct.
+const EVENT_ATTR: &str = "event";
+
 /// This object will collect all the information we need to keep while parsing the `Runtime` struct.
 #[derive(Debug)]
 pub struct RuntimeDef {
@@ -7,7 +16,22 @@ pub struct RuntimeDef {
 	pub runtime_struct: syn::Ident,
 	/// This is the list of pallets included in the `Runtime` struct. We omit `system` from this
 	/// list, but during parsing we check that system exists.
-	pub pallets: Vec<(syn::Ident, syn::Type)>,
+	pub pallets: Vec<PalletDef>,
+}
+
+/// This is the metadata we keep about each pallet included in the `Runtime` struct.
+#[derive(Debug)]
+pub struct PalletDef {
+	/// The name of the field storing the pallet.
+	pub name: syn::Ident,
+	/// The type of the pallet.
+	pub ty: syn::Type,
+	/// Whether the pallet provides an inherent, which is marked with `#[inherent]`.
+	pub inherent: bool,
+	/// Whether the pallet implements hooks, which is marked with `#[hooks]`.
+	pub hooks: bool,
+	/// Whether the pallet emits events, which is marked with `#[event]`.
+	pub event: bool,
 }
 
 impl RuntimeDef {
@@ -28,8 +52,13 @@ impl RuntimeDef {
 		let mut pallets = vec![];
 		// We skip `system`, which we ensure is the first field in `check_system`.
 		for field in item_struct.fields.into_iter().skip(1) {
+			let has_attr = |name| field.attrs.iter().any(|attr| attr.path().is_ident(name));
+			let inherent = has_attr(INHERENT_ATTR);
+			let hooks = has_attr(HOOKS_ATTR);
+			let event = has_attr(EVENT_ATTR);
 			if let Some(ident) = field.ident {
-				pallets.push((ident, field.ty))
+				let ty = replace_self(&field.ty, &runtime_struct)?;
+				pallets.push(PalletDef { name: ident, ty, inherent, hooks, event })
 			}
 		}
 
@@ -37,6 +66,42 @@ impl RuntimeDef {
 	}
 }
 
+/// Replace `Self` with the name of the runtime struct in the type of a pallet, like
+/// `balances::Pallet<Self>`, so that the type can be used outside of the struct definition.
+fn replace_self(ty: &syn::Type, runtime_struct: &syn::Ident) -> syn::Result<syn::Type> {
+	fn replace(
+		tokens: proc_macro2::TokenStream,
+		runtime_struct: &syn::Ident,
+	) -> proc_macro2::TokenStream {
+		tokens
+			.into_iter()
+			.map(|token| match token {
+				proc_macro2::TokenTree::Ident(ident) if ident == "Self" =>
+					proc_macro2::TokenTree::Ident(runtime_struct.clone()),
+				proc_macro2::TokenTree::Group(group) => {
+					let stream = replace(group.stream(), runtime_struct);
+					proc_macro2::TokenTree::Group(proc_macro2::Group::new(group.delimiter(), stream))
+				},
+				token => token,
+			})
+			.collect()
+	}
+
+	syn::parse2(replace(ty.to_token_stream(), runtime_struct))
+}
+
+/// Remove the attributes we use to mark pallets, like `#[inherent]` or `#[event]`, from the fields
+/// of the `Runtime` struct, since they are not real attributes and would not compile.
+pub fn remove_pallet_attrs(item: &mut syn::Item) {
+	if let syn::Item::Struct(item_struct) = item {
+		for field in item_struct.fields.iter_mut() {
+			field.attrs.retain(|attr| {
+				![INHERENT_ATTR, HOOKS_ATTR, EVENT_ATTR].iter().any(|name| attr.path().is_ident(name))
+			});
+		}
+	}
+}
+
 /// This function checks that the `system` pallet is the first pallet included in the `Runtime`
 /// struct. We make many assumptions about the `system` pallet in order to keep these macros simple.
 /// For example, we assume that the system pallet has no callable functions, and that it contains
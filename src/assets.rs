@@ -0,0 +1,686 @@
+use core::fmt::Debug;
+use std::collections::BTreeMap;
+
+use num::{CheckedAdd, CheckedSub, Zero};
+
+use crate::support::{self, Currency, DispatchResult, ReservableCurrency};
+
+/// The type of the deposits of the assets.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config: Sized + crate::system::Config + AsMut<Pallet<Self>> {
+	/// The currency the deposits of the assets are reserved in.
+	type Currency: ReservableCurrency<Self, Self::AccountId>;
+	/// The deposit reserved from the account which creates an asset.
+	const ASSET_DEPOSIT: BalanceOf<Self>;
+	/// The identifier of an asset.
+	type AssetId: Ord + Copy + Debug;
+	/// The type of the balances of the assets, which is separate from the native currency.
+	type AssetBalance: Zero + CheckedSub + CheckedAdd + Copy + Ord + Debug;
+	/// The maximum length of the name and the symbol of an asset.
+	const STRING_LIMIT: u32;
+}
+
+/// The events emitted by the Assets Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// The asset `asset_id` was created by `creator`, and is managed by `admin`.
+	Created { asset_id: T::AssetId, creator: T::AccountId, admin: T::AccountId },
+	/// The metadata of the asset `asset_id` was set.
+	MetadataSet { asset_id: T::AssetId, name: Vec<u8>, symbol: Vec<u8>, decimals: u8 },
+	/// `amount` of the asset `asset_id` was minted to `owner`.
+	Issued { asset_id: T::AssetId, owner: T::AccountId, amount: T::AssetBalance },
+	/// `amount` of the asset `asset_id` was burned from `owner`.
+	Burned { asset_id: T::AssetId, owner: T::AccountId, amount: T::AssetBalance },
+	/// `amount` of the asset `asset_id` was transferred from `from` to `to`.
+	Transferred {
+		asset_id: T::AssetId,
+		from: T::AccountId,
+		to: T::AccountId,
+		amount: T::AssetBalance,
+	},
+	/// The account `who` can no longer transfer the asset `asset_id`.
+	Frozen { asset_id: T::AssetId, who: T::AccountId },
+	/// The account `who` can transfer the asset `asset_id` again.
+	Thawed { asset_id: T::AssetId, who: T::AccountId },
+	/// No account can transfer the asset `asset_id`.
+	AssetFrozen { asset_id: T::AssetId },
+	/// The accounts can transfer the asset `asset_id` again.
+	AssetThawed { asset_id: T::AssetId },
+	/// `source` approved `delegate` to transfer `amount` of its asset `asset_id`.
+	ApprovedTransfer {
+		asset_id: T::AssetId,
+		source: T::AccountId,
+		delegate: T::AccountId,
+		amount: T::AssetBalance,
+	},
+	/// `owner` cancelled the approval of `delegate` to transfer its asset `asset_id`.
+	ApprovalCancelled { asset_id: T::AssetId, owner: T::AccountId, delegate: T::AccountId },
+	/// `delegate` transferred `amount` of the asset `asset_id` of `owner` to `destination`.
+	TransferredApproved {
+		asset_id: T::AssetId,
+		owner: T::AccountId,
+		delegate: T::AccountId,
+		destination: T::AccountId,
+		amount: T::AssetBalance,
+	},
+}
+
+/// The details of an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetDetails<T: Config> {
+	/// The account which created the asset, and can set its metadata.
+	pub owner: T::AccountId,
+	/// The account which can mint, burn, freeze and thaw the asset.
+	pub admin: T::AccountId,
+	/// The deposit reserved from the owner for the asset.
+	pub deposit: BalanceOf<T>,
+	/// The total amount of the asset held by all the accounts.
+	pub supply: T::AssetBalance,
+	/// The minimum balance of the asset an account can hold, if it holds any.
+	pub min_balance: T::AssetBalance,
+	/// Whether the asset is frozen, so that no account can transfer it.
+	pub is_frozen: bool,
+}
+
+/// The metadata of an asset, which is only informative.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetMetadata {
+	/// The name of the asset.
+	pub name: Vec<u8>,
+	/// The symbol of the asset, used by wallets.
+	pub symbol: Vec<u8>,
+	/// The number of decimals wallets display the balances of the asset with.
+	pub decimals: u8,
+}
+
+/// The holding of an asset by an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetAccount<T: Config> {
+	/// The balance of the asset held by the account.
+	pub balance: T::AssetBalance,
+	/// Whether the account is frozen, so that it cannot transfer the asset.
+	pub is_frozen: bool,
+}
+
+/// The key of an approval: the asset, the owner of the funds and the delegate spending them.
+type ApprovalKey<T> = (
+	<T as Config>::AssetId,
+	<T as crate::system::Config>::AccountId,
+	<T as crate::system::Config>::AccountId,
+);
+
+/// This is the Assets Module.
+/// It lets accounts create fungible assets, separate from the native currency of the balances
+/// pallet. Each asset has an admin which can mint, burn and freeze it.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The details of each asset.
+	assets: BTreeMap<T::AssetId, AssetDetails<T>>,
+	/// The metadata of each asset which has some.
+	metadata: BTreeMap<T::AssetId, AssetMetadata>,
+	/// The holdings of each account, by asset.
+	accounts: BTreeMap<(T::AssetId, T::AccountId), AssetAccount<T>>,
+	/// The amounts delegates are approved to transfer, by asset, owner and delegate.
+	approvals: BTreeMap<ApprovalKey<T>, T::AssetBalance>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Assets Module.
+	pub fn new() -> Self {
+		Self {
+			assets: BTreeMap::new(),
+			metadata: BTreeMap::new(),
+			accounts: BTreeMap::new(),
+			approvals: BTreeMap::new(),
+			events: Vec::new(),
+		}
+	}
+
+	/// Get the details of the asset `id`.
+	pub fn asset(&self, id: T::AssetId) -> Option<&AssetDetails<T>> {
+		self.assets.get(&id)
+	}
+
+	/// Get the metadata of the asset `id`.
+	pub fn metadata(&self, id: T::AssetId) -> Option<&AssetMetadata> {
+		self.metadata.get(&id)
+	}
+
+	/// Get the balance of the asset `id` held by `who`.
+	pub fn balance(&self, id: T::AssetId, who: &T::AccountId) -> T::AssetBalance {
+		self.accounts
+			.get(&(id, who.clone()))
+			.map(|account| account.balance)
+			.unwrap_or(T::AssetBalance::zero())
+	}
+
+	/// Get the amount of the asset `id` of `owner` which `delegate` is approved to transfer.
+	pub fn allowance(
+		&self,
+		id: T::AssetId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+	) -> T::AssetBalance {
+		*self
+			.approvals
+			.get(&(id, owner.clone(), delegate.clone()))
+			.unwrap_or(&T::AssetBalance::zero())
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// Get the details of the asset `id`, checking that `who` is its admin.
+	fn ensure_admin(
+		&self,
+		id: T::AssetId,
+		who: &T::AccountId,
+	) -> Result<&AssetDetails<T>, &'static str> {
+		let details = self.assets.get(&id).ok_or("Asset does not exist.")?;
+		if details.admin != *who {
+			return Err("Caller is not the admin of the asset.");
+		}
+		Ok(details)
+	}
+
+	/// Set the balance of the asset `id` held by `who`, removing the holding if it is empty.
+	/// Accounts must hold either nothing or at least the minimum balance of the asset.
+	fn set_account_balance(
+		&mut self,
+		id: T::AssetId,
+		who: &T::AccountId,
+		min_balance: T::AssetBalance,
+		balance: T::AssetBalance,
+	) -> DispatchResult {
+		if balance.is_zero() {
+			self.accounts.remove(&(id, who.clone()));
+			return Ok(());
+		}
+		if balance < min_balance {
+			return Err("Balance is below the minimum balance of the asset.");
+		}
+		self.accounts
+			.entry((id, who.clone()))
+			.or_insert(AssetAccount { balance, is_frozen: false })
+			.balance = balance;
+		Ok(())
+	}
+
+	/// Move `amount` of the asset `id` from `from` to `to`, if neither the asset nor `from` are
+	/// frozen.
+	fn do_transfer(
+		&mut self,
+		id: T::AssetId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::AssetBalance,
+	) -> DispatchResult {
+		let details = self.assets.get(&id).ok_or("Asset does not exist.")?;
+		if details.is_frozen {
+			return Err("Asset is frozen.");
+		}
+		let min_balance = details.min_balance;
+		if self.accounts.get(&(id, from.clone())).is_some_and(|account| account.is_frozen) {
+			return Err("Account is frozen.");
+		}
+		if from == to {
+			return Ok(());
+		}
+
+		let new_from_balance =
+			self.balance(id, from).checked_sub(&amount).ok_or("Not enough funds!")?;
+		let new_to_balance = self.balance(id, to).checked_add(&amount).ok_or("Overflow")?;
+		if !new_from_balance.is_zero() && new_from_balance < min_balance
+			|| new_to_balance < min_balance
+		{
+			return Err("Balance is below the minimum balance of the asset.");
+		}
+		self.set_account_balance(id, from, min_balance, new_from_balance)?;
+		self.set_account_balance(id, to, min_balance, new_to_balance)
+	}
+
+	/// Set whether the account `who` is frozen for the asset `id`.
+	fn set_account_frozen(
+		&mut self,
+		caller: &T::AccountId,
+		id: T::AssetId,
+		who: &T::AccountId,
+		is_frozen: bool,
+	) -> DispatchResult {
+		self.ensure_admin(id, caller)?;
+		let account = self.accounts.get_mut(&(id, who.clone())).ok_or("Account holds no asset.")?;
+		account.is_frozen = is_frozen;
+		Ok(())
+	}
+
+	/// Set whether the asset `id` is frozen.
+	fn set_asset_frozen(
+		&mut self,
+		caller: &T::AccountId,
+		id: T::AssetId,
+		is_frozen: bool,
+	) -> DispatchResult {
+		self.ensure_admin(id, caller)?;
+		self.assets.get_mut(&id).expect("The asset exists.").is_frozen = is_frozen;
+		Ok(())
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Create the asset `id`, managed by `admin`, reserving `ASSET_DEPOSIT` from the caller.
+	/// Accounts must hold either nothing or at least `min_balance` of it.
+	pub fn create(
+		runtime: &mut T,
+		caller: T::AccountId,
+		id: T::AssetId,
+		admin: T::AccountId,
+		min_balance: T::AssetBalance,
+	) -> DispatchResult {
+		let assets: &mut Self = runtime.as_mut();
+		if assets.assets.contains_key(&id) {
+			return Err("Asset already exists.");
+		}
+		if min_balance.is_zero() {
+			return Err("Minimum balance must not be zero.");
+		}
+		T::Currency::reserve(runtime, &caller, T::ASSET_DEPOSIT)?;
+
+		let details = AssetDetails {
+			owner: caller.clone(),
+			admin: admin.clone(),
+			deposit: T::ASSET_DEPOSIT,
+			supply: T::AssetBalance::zero(),
+			min_balance,
+			is_frozen: false,
+		};
+		let assets: &mut Self = runtime.as_mut();
+		assets.assets.insert(id, details);
+		assets.deposit_event(Event::Created { asset_id: id, creator: caller, admin });
+		Ok(())
+	}
+
+	/// Set the metadata of the asset `id`. Only the owner of the asset can set it.
+	pub fn set_metadata(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
+	) -> DispatchResult {
+		let details = self.assets.get(&id).ok_or("Asset does not exist.")?;
+		if details.owner != caller {
+			return Err("Caller is not the owner of the asset.");
+		}
+		if name.len() > T::STRING_LIMIT as usize || symbol.len() > T::STRING_LIMIT as usize {
+			return Err("Metadata is too long.");
+		}
+		let metadata = AssetMetadata { name: name.clone(), symbol: symbol.clone(), decimals };
+		self.metadata.insert(id, metadata);
+		self.deposit_event(Event::MetadataSet { asset_id: id, name, symbol, decimals });
+		Ok(())
+	}
+
+	/// Mint `amount` of the asset `id` to `beneficiary`. Only the admin of the asset can mint it.
+	pub fn mint(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		beneficiary: T::AccountId,
+		amount: T::AssetBalance,
+	) -> DispatchResult {
+		let details = self.ensure_admin(id, &caller)?;
+		let min_balance = details.min_balance;
+		let supply = details.supply.checked_add(&amount).ok_or("Overflow")?;
+		let balance = self.balance(id, &beneficiary).checked_add(&amount).ok_or("Overflow")?;
+		self.set_account_balance(id, &beneficiary, min_balance, balance)?;
+		self.assets.get_mut(&id).expect("The asset exists.").supply = supply;
+		self.deposit_event(Event::Issued { asset_id: id, owner: beneficiary, amount });
+		Ok(())
+	}
+
+	/// Burn `amount` of the asset `id` held by `who`. Only the admin of the asset can burn it.
+	pub fn burn(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		who: T::AccountId,
+		amount: T::AssetBalance,
+	) -> DispatchResult {
+		let details = self.ensure_admin(id, &caller)?;
+		let min_balance = details.min_balance;
+		let supply = details.supply.checked_sub(&amount).ok_or("Not enough funds!")?;
+		let balance = self.balance(id, &who).checked_sub(&amount).ok_or("Not enough funds!")?;
+		self.set_account_balance(id, &who, min_balance, balance)?;
+		self.assets.get_mut(&id).expect("The asset exists.").supply = supply;
+		self.deposit_event(Event::Burned { asset_id: id, owner: who, amount });
+		Ok(())
+	}
+
+	/// Transfer `amount` of the asset `id` from the caller to `target`.
+	pub fn transfer(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		target: T::AccountId,
+		amount: T::AssetBalance,
+	) -> DispatchResult {
+		self.do_transfer(id, &caller, &target, amount)?;
+		self.deposit_event(Event::Transferred { asset_id: id, from: caller, to: target, amount });
+		Ok(())
+	}
+
+	/// Prevent `who` from transferring the asset `id`. Only the admin of the asset can freeze it.
+	pub fn freeze(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		who: T::AccountId,
+	) -> DispatchResult {
+		self.set_account_frozen(&caller, id, &who, true)?;
+		self.deposit_event(Event::Frozen { asset_id: id, who });
+		Ok(())
+	}
+
+	/// Allow `who` to transfer the asset `id` again. Only the admin of the asset can thaw it.
+	pub fn thaw(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		who: T::AccountId,
+	) -> DispatchResult {
+		self.set_account_frozen(&caller, id, &who, false)?;
+		self.deposit_event(Event::Thawed { asset_id: id, who });
+		Ok(())
+	}
+
+	/// Prevent all accounts from transferring the asset `id`. Only the admin of the asset can
+	/// freeze it.
+	pub fn freeze_asset(&mut self, caller: T::AccountId, id: T::AssetId) -> DispatchResult {
+		self.set_asset_frozen(&caller, id, true)?;
+		self.deposit_event(Event::AssetFrozen { asset_id: id });
+		Ok(())
+	}
+
+	/// Allow all accounts to transfer the asset `id` again. Only the admin of the asset can thaw
+	/// it.
+	pub fn thaw_asset(&mut self, caller: T::AccountId, id: T::AssetId) -> DispatchResult {
+		self.set_asset_frozen(&caller, id, false)?;
+		self.deposit_event(Event::AssetThawed { asset_id: id });
+		Ok(())
+	}
+
+	/// Approve `delegate` to transfer `amount` more of the asset `id` of the caller.
+	pub fn approve_transfer(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		delegate: T::AccountId,
+		amount: T::AssetBalance,
+	) -> DispatchResult {
+		if !self.assets.contains_key(&id) {
+			return Err("Asset does not exist.");
+		}
+		let approved =
+			self.allowance(id, &caller, &delegate).checked_add(&amount).ok_or("Overflow")?;
+		self.approvals.insert((id, caller.clone(), delegate.clone()), approved);
+		self.deposit_event(Event::ApprovedTransfer {
+			asset_id: id,
+			source: caller,
+			delegate,
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Cancel the approval of `delegate` to transfer the asset `id` of the caller.
+	pub fn cancel_approval(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		delegate: T::AccountId,
+	) -> DispatchResult {
+		self.approvals
+			.remove(&(id, caller.clone(), delegate.clone()))
+			.ok_or("Approval does not exist.")?;
+		self.deposit_event(Event::ApprovalCancelled { asset_id: id, owner: caller, delegate });
+		Ok(())
+	}
+
+	/// Transfer `amount` of the asset `id` of `owner` to `destination`, using an approval of the
+	/// caller.
+	pub fn transfer_approved(
+		&mut self,
+		caller: T::AccountId,
+		id: T::AssetId,
+		owner: T::AccountId,
+		destination: T::AccountId,
+		amount: T::AssetBalance,
+	) -> DispatchResult {
+		let remaining = self
+			.allowance(id, &owner, &caller)
+			.checked_sub(&amount)
+			.ok_or("Transfer exceeds the approved amount.")?;
+		self.do_transfer(id, &owner, &destination, amount)?;
+
+		let key = (id, owner.clone(), caller.clone());
+		if remaining.is_zero() {
+			self.approvals.remove(&key);
+		} else {
+			self.approvals.insert(key, remaining);
+		}
+		self.deposit_event(Event::TransferredApproved {
+			asset_id: id,
+			owner,
+			delegate: caller,
+			destination,
+			amount,
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Event;
+	use crate::{assets, balances};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[event]
+		assets: assets::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = &'static str;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl assets::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		type AssetId = u32;
+		type AssetBalance = u64;
+		const ASSET_DEPOSIT: u32 = 10;
+		const STRING_LIMIT: u32 = 8;
+	}
+
+	crate::testing::impl_dispatch!(assets);
+
+	fn create_asset(runtime: &mut Runtime) {
+		runtime.balances.set_balance("owner", 100).unwrap();
+		let create = assets::Call::create { id: 0, admin: "admin", min_balance: 10 };
+		dispatch(runtime, "owner", create).unwrap();
+		let mint = assets::Call::mint { id: 0, beneficiary: "alice", amount: 100 };
+		dispatch(runtime, "admin", mint).unwrap();
+	}
+
+	#[test]
+	fn create_mint_and_burn() {
+		let mut runtime = Runtime::new();
+		create_asset(&mut runtime);
+		assert_eq!(
+			dispatch(
+				&mut runtime,
+				"alice",
+				assets::Call::create { id: 0, admin: "alice", min_balance: 1 }
+			),
+			Err("Asset already exists.")
+		);
+		// The owner holds a deposit for the asset.
+		assert_eq!(runtime.balances.reserved_balance(&"owner"), 10);
+		assert_eq!(runtime.assets.asset(0).unwrap().deposit, 10);
+		assert_eq!(
+			dispatch(
+				&mut runtime,
+				"bob",
+				assets::Call::create { id: 1, admin: "bob", min_balance: 1 }
+			),
+			Err("Not enough funds!")
+		);
+		assert!(runtime.assets.asset(1).is_none());
+		assert_eq!(runtime.assets.balance(0, &"alice"), 100);
+		assert_eq!(runtime.assets.asset(0).unwrap().supply, 100);
+
+		// Only the owner sets the metadata.
+		let set_metadata = |name: &[u8]| assets::Call::set_metadata {
+			id: 0,
+			name: name.to_vec(),
+			symbol: b"LOY".to_vec(),
+			decimals: 2,
+		};
+		assert_eq!(
+			dispatch(&mut runtime, "admin", set_metadata(b"Loyalty")),
+			Err("Caller is not the owner of the asset.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "owner", set_metadata(b"Loyalty points")),
+			Err("Metadata is too long.")
+		);
+		dispatch(&mut runtime, "owner", set_metadata(b"Loyalty")).unwrap();
+		assert_eq!(runtime.assets.metadata(0).unwrap().symbol, b"LOY");
+
+		// Only the admin mints and burns, and accounts can't be left with less than the minimum
+		// balance.
+		let mint = |amount| assets::Call::mint { id: 0, beneficiary: "bob", amount };
+		assert_eq!(
+			dispatch(&mut runtime, "owner", mint(50)),
+			Err("Caller is not the admin of the asset.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "admin", mint(5)),
+			Err("Balance is below the minimum balance of the asset.")
+		);
+		let burn = |amount| assets::Call::burn { id: 0, who: "alice", amount };
+		assert_eq!(
+			dispatch(&mut runtime, "admin", burn(95)),
+			Err("Balance is below the minimum balance of the asset.")
+		);
+		dispatch(&mut runtime, "admin", burn(100)).unwrap();
+		assert_eq!(runtime.assets.balance(0, &"alice"), 0);
+		assert_eq!(runtime.assets.asset(0).unwrap().supply, 0);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::assets(Event::Burned { asset_id: 0, owner: "alice", amount: 100 }))
+		));
+	}
+
+	#[test]
+	fn transfer_and_freeze() {
+		let mut runtime = Runtime::new();
+		create_asset(&mut runtime);
+
+		let transfer = |target, amount| assets::Call::transfer { id: 0, target, amount };
+		assert_eq!(
+			dispatch(&mut runtime, "alice", transfer("bob", 5)),
+			Err("Balance is below the minimum balance of the asset.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "alice", transfer("bob", 95)),
+			Err("Balance is below the minimum balance of the asset.")
+		);
+		dispatch(&mut runtime, "alice", transfer("bob", 40)).unwrap();
+		assert_eq!(runtime.assets.balance(0, &"alice"), 60);
+		assert_eq!(runtime.assets.balance(0, &"bob"), 40);
+
+		// A frozen account can't send the asset, but can still receive it.
+		dispatch(&mut runtime, "admin", assets::Call::freeze { id: 0, who: "bob" }).unwrap();
+		assert_eq!(dispatch(&mut runtime, "bob", transfer("alice", 10)), Err("Account is frozen."));
+		dispatch(&mut runtime, "alice", transfer("bob", 10)).unwrap();
+		dispatch(&mut runtime, "admin", assets::Call::thaw { id: 0, who: "bob" }).unwrap();
+		dispatch(&mut runtime, "bob", transfer("alice", 10)).unwrap();
+
+		// No one can send a frozen asset.
+		dispatch(&mut runtime, "admin", assets::Call::freeze_asset { id: 0 }).unwrap();
+		assert_eq!(dispatch(&mut runtime, "alice", transfer("bob", 10)), Err("Asset is frozen."));
+		dispatch(&mut runtime, "admin", assets::Call::thaw_asset { id: 0 }).unwrap();
+		dispatch(&mut runtime, "alice", transfer("bob", 60)).unwrap();
+		assert_eq!(runtime.assets.balance(0, &"bob"), 100);
+		assert_eq!(runtime.assets.asset(0).unwrap().supply, 100);
+	}
+
+	#[test]
+	fn approved_transfers() {
+		let mut runtime = Runtime::new();
+		create_asset(&mut runtime);
+
+		let approve = assets::Call::approve_transfer { id: 0, delegate: "bob", amount: 50 };
+		dispatch(&mut runtime, "alice", approve).unwrap();
+		assert_eq!(runtime.assets.allowance(0, &"alice", &"bob"), 50);
+
+		let transfer_approved = |amount| assets::Call::transfer_approved {
+			id: 0,
+			owner: "alice",
+			destination: "charlie",
+			amount,
+		};
+		assert_eq!(
+			dispatch(&mut runtime, "bob", transfer_approved(60)),
+			Err("Transfer exceeds the approved amount.")
+		);
+		dispatch(&mut runtime, "bob", transfer_approved(30)).unwrap();
+		assert_eq!(runtime.assets.balance(0, &"charlie"), 30);
+		assert_eq!(runtime.assets.allowance(0, &"alice", &"bob"), 20);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::assets(Event::TransferredApproved {
+				delegate: "bob",
+				amount: 30,
+				..
+			}))
+		));
+
+		let cancel = assets::Call::cancel_approval { id: 0, delegate: "bob" };
+		dispatch(&mut runtime, "alice", cancel.clone()).unwrap();
+		assert_eq!(dispatch(&mut runtime, "alice", cancel), Err("Approval does not exist."));
+		assert_eq!(
+			dispatch(&mut runtime, "bob", transfer_approved(10)),
+			Err("Transfer exceeds the approved amount.")
+		);
+	}
+}
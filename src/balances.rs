@@ -1,59 +1,705 @@
+use core::fmt::Debug;
 use num::{CheckedAdd, CheckedSub, Zero};
 use std::collections::BTreeMap;
 
-pub trait Config: crate::system::Config {
-	type Balance: Zero + CheckedSub + CheckedAdd + Copy;
+use crate::{
+	support::{self, DispatchResult},
+	system::OriginFor,
+};
+
+pub trait Config: Sized + crate::system::Config {
+	type Balance: Zero + CheckedSub + CheckedAdd + Copy + Ord + Debug;
+	/// The handler of the dust of the accounts which are reaped.
+	type DustRemoval: crate::support::OnUnbalanced<Self, Self::Balance>;
+	/// The minimum balance an account must have to exist. Accounts whose balance falls below it
+	/// are reaped, and what is left of their balance is dust.
+	const EXISTENTIAL_DEPOSIT: Self::Balance;
+}
+
+pub use crate::support::{BalanceStatus, LockIdentifier};
+
+/// The events emitted by the Balances Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// `amount` was transferred from `from` to `to`.
+	Transfer { from: T::AccountId, to: T::AccountId, amount: T::Balance },
+	/// `amount` was transferred from `from` to `to`, keeping `from` alive.
+	TransferKeepAlive { from: T::AccountId, to: T::AccountId, amount: T::Balance },
+	/// All the transferable funds of `from`, `amount`, were transferred to `to`.
+	TransferAll { from: T::AccountId, to: T::AccountId, amount: T::Balance },
+	/// Root transferred `amount` from `from` to `to`.
+	ForceTransfer { from: T::AccountId, to: T::AccountId, amount: T::Balance },
+	/// `owner` allowed `spender` to transfer up to `amount` of its funds.
+	Approval { owner: T::AccountId, spender: T::AccountId, amount: T::Balance },
+	/// `spender` transferred `amount` of the funds of `owner` to `to`, using its allowance.
+	TransferFrom {
+		owner: T::AccountId,
+		spender: T::AccountId,
+		to: T::AccountId,
+		amount: T::Balance,
+	},
+	/// Root created `amount` of new funds in the free balance of `who`.
+	Minted { who: T::AccountId, amount: T::Balance },
+	/// Root destroyed `amount` of the free balance of `who`.
+	Burned { who: T::AccountId, amount: T::Balance },
+	/// Root set the free balance of `who` to `free`.
+	BalanceSet { who: T::AccountId, free: T::Balance },
 }
 
 /// This is the Balances Module.
 /// It is a simple module which keeps track of how much balance each account has in this state
 /// machine.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pallet<T: Config> {
 	// A simple storage mapping from accounts (`String`) to their balances (`u128`).
 	balances: BTreeMap<T::AccountId, T::Balance>,
+	/// The reserved balance of each account, which is held by some pallet, like a deposit.
+	reserved: BTreeMap<T::AccountId, T::Balance>,
+	/// The locks on the balance of each account, by their identifier.
+	locks: BTreeMap<T::AccountId, BTreeMap<LockIdentifier, T::Balance>>,
+	/// The accounts reaped during the current block, whose state in other pallets is removed at
+	/// the end of the block.
+	reaped: Vec<T::AccountId>,
+	/// The dust of the accounts reaped during the current block, which is handled at the end of
+	/// the block.
+	dust: T::Balance,
+	/// The total amount of funds in existence, free and reserved, over all accounts.
+	total_issuance: T::Balance,
+	/// The amount each spender is allowed to transfer from each owner, by `(owner, spender)`.
+	allowances: BTreeMap<(T::AccountId, T::AccountId), T::Balance>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
 }
 
 impl<T: Config> Pallet<T> {
 	/// Create a new instance of the balances module.
 	pub fn new() -> Self {
-		Self { balances: BTreeMap::new() }
+		Self {
+			balances: BTreeMap::new(),
+			reserved: BTreeMap::new(),
+			locks: BTreeMap::new(),
+			reaped: Vec::new(),
+			dust: T::Balance::zero(),
+			total_issuance: T::Balance::zero(),
+			allowances: BTreeMap::new(),
+			events: Vec::new(),
+		}
+	}
+
+	/// Set the free balance of an account `who` to some `amount`, minting or burning the
+	/// difference. If the account ends up with less than the existential deposit, it is reaped and
+	/// its balance is simply burned, rather than handled as dust.
+	pub fn set_balance(&mut self, who: T::AccountId, amount: T::Balance) -> DispatchResult {
+		let before = self.total_balance(&who);
+		let total = self.reserved_balance(&who).checked_add(&amount).ok_or("Overflow")?;
+		let reaped = total < T::EXISTENTIAL_DEPOSIT;
+		let after = if reaped { T::Balance::zero() } else { total };
+		if after > before {
+			self.mint_issuance(after.checked_sub(&before).unwrap_or(after))?;
+		} else {
+			self.burn_issuance(before.checked_sub(&after).unwrap_or(before))?;
+		}
+
+		self.write_free(&who, amount);
+		if reaped {
+			self.reap_account(who);
+		}
+		Ok(())
+	}
+
+	/// Get the total amount of funds in existence.
+	pub fn total_issuance(&self) -> T::Balance {
+		self.total_issuance
 	}
 
-	/// Set the balance of an account `who` to some `amount`.
-	pub fn set_balance(&mut self, who: T::AccountId, amount: T::Balance) {
-		self.balances.insert(who, amount);
+	/// Get the amount `spender` is allowed to transfer from the funds of `owner`.
+	pub fn allowance(&self, owner: &T::AccountId, spender: &T::AccountId) -> T::Balance {
+		*self
+			.allowances
+			.get(&(owner.clone(), spender.clone()))
+			.unwrap_or(&T::Balance::zero())
 	}
 
-	/// Get the balance of an account `who`.
+	/// Check that the total issuance is the sum of the free and reserved balances of all
+	/// accounts.
+	pub fn check_issuance(&self) -> DispatchResult {
+		let total = self
+			.balances
+			.values()
+			.chain(self.reserved.values())
+			.try_fold(T::Balance::zero(), |total, balance| total.checked_add(balance))
+			.ok_or("Overflow")?;
+		if total != self.total_issuance {
+			return Err("The total issuance does not match the balances.");
+		}
+		Ok(())
+	}
+
+	/// Get the free balance of an account `who`, which can be transferred unless it is locked.
 	/// If the account has no stored balance, we return zero.
 	pub fn balance(&self, who: &T::AccountId) -> T::Balance {
 		*self.balances.get(who).unwrap_or(&T::Balance::zero())
 	}
+
+	/// Get the reserved balance of an account `who`, which cannot be transferred.
+	pub fn reserved_balance(&self, who: &T::AccountId) -> T::Balance {
+		*self.reserved.get(who).unwrap_or(&T::Balance::zero())
+	}
+
+	/// Get the total balance of an account `who`, free and reserved.
+	pub fn total_balance(&self, who: &T::AccountId) -> T::Balance {
+		let free = self.balance(who);
+		free.checked_add(&self.reserved_balance(who)).unwrap_or(free)
+	}
+
+	/// Move `amount` of the free balance of `who` to its reserved balance, if it is not locked.
+	pub fn reserve(&mut self, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let free = self.balance(who).checked_sub(&amount).ok_or("Not enough funds!")?;
+		if free < self.locked(who) {
+			return Err("Not enough unlocked funds!");
+		}
+		let reserved = self.reserved_balance(who).checked_add(&amount).ok_or("Overflow")?;
+		self.write_free(who, free);
+		self.write_reserved(who, reserved);
+		Ok(())
+	}
+
+	/// Move up to `amount` of the reserved balance of `who` back to its free balance. Returns the
+	/// amount actually unreserved.
+	pub fn unreserve(&mut self, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let reserved = self.reserved_balance(who);
+		let amount = amount.min(reserved);
+		let Some(free) = self.balance(who).checked_add(&amount) else {
+			return T::Balance::zero();
+		};
+		self.write_free(who, free);
+		self.write_reserved(who, reserved.checked_sub(&amount).unwrap_or(reserved));
+		amount
+	}
+
+	/// Remove up to `amount` of the free balance of `who`, even if it is locked, reaping the account
+	/// if it falls below the existential deposit. Returns the amount actually slashed, which the
+	/// caller should hand to some `OnUnbalanced` handler.
+	pub fn slash(&mut self, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let free = self.balance(who);
+		let amount = amount.min(free);
+		if self.burn_issuance(amount).is_err() {
+			return T::Balance::zero();
+		}
+		self.write_free(who, free.checked_sub(&amount).unwrap_or(free));
+		// What is left of the balance of `who` is still part of the total issuance, so it can be
+		// burned as dust.
+		let _ = self.reap_if_dust(who);
+		amount
+	}
+
+	/// Remove up to `amount` of the reserved balance of `who`, reaping the account if it falls
+	/// below the existential deposit. Returns the amount actually slashed, which the caller should
+	/// hand to some `OnUnbalanced` handler.
+	pub fn slash_reserved(&mut self, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let reserved = self.reserved_balance(who);
+		let amount = amount.min(reserved);
+		if self.burn_issuance(amount).is_err() {
+			return T::Balance::zero();
+		}
+		self.write_reserved(who, reserved.checked_sub(&amount).unwrap_or(reserved));
+		// What is left of the balance of `who` is still part of the total issuance, so it can be
+		// burned as dust.
+		let _ = self.reap_if_dust(who);
+		amount
+	}
+
+	/// Move up to `amount` of the reserved balance of `slashed` to the free or reserved balance
+	/// of `beneficiary`, depending on `status`. Returns the amount actually moved.
+	pub fn repatriate_reserved(
+		&mut self,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+		status: BalanceStatus,
+	) -> Result<T::Balance, &'static str> {
+		if slashed == beneficiary {
+			return Ok(match status {
+				BalanceStatus::Free => self.unreserve(slashed, amount),
+				BalanceStatus::Reserved => amount.min(self.reserved_balance(slashed)),
+			});
+		}
+		let reserved = self.reserved_balance(slashed);
+		let amount = amount.min(reserved);
+		let total = self.total_balance(beneficiary).checked_add(&amount).ok_or("Overflow")?;
+		if total < T::EXISTENTIAL_DEPOSIT {
+			return Err("Balance is below the existential deposit.");
+		}
+
+		match status {
+			BalanceStatus::Free => {
+				let free = self.balance(beneficiary).checked_add(&amount).ok_or("Overflow")?;
+				self.write_free(beneficiary, free);
+			},
+			BalanceStatus::Reserved => {
+				let reserved =
+					self.reserved_balance(beneficiary).checked_add(&amount).ok_or("Overflow")?;
+				self.write_reserved(beneficiary, reserved);
+			},
+		}
+		self.write_reserved(slashed, reserved.checked_sub(&amount).unwrap_or(reserved));
+		self.reap_if_dust(slashed)?;
+		Ok(amount)
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// Remove `amount` from the free balance of `who`, burning it, if it is not locked. The
+	/// account is reaped if it falls below the existential deposit.
+	pub fn withdraw(&mut self, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let free = self.balance(who).checked_sub(&amount).ok_or("Not enough funds!")?;
+		if free < self.locked(who) {
+			return Err("Not enough unlocked funds!");
+		}
+		self.burn_issuance(amount)?;
+		self.write_free(who, free);
+		self.reap_if_dust(who)
+	}
+
+	/// Add `amount` to the free balance of `who`, minting it. The account must end up with at
+	/// least the existential deposit.
+	pub fn deposit_creating(&mut self, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let free = self.balance(who).checked_add(&amount).ok_or("Overflow")?;
+		let total = self.reserved_balance(who).checked_add(&free).ok_or("Overflow")?;
+		if total < T::EXISTENTIAL_DEPOSIT {
+			return Err("Balance is below the existential deposit.");
+		}
+		let total_issuance = self.total_issuance.checked_add(&amount).ok_or("Overflow")?;
+		self.write_free(who, free);
+		self.total_issuance = total_issuance;
+		Ok(())
+	}
+
+	/// Move `amount` of the free balance of `from` to `to`. The sending account is reaped if it
+	/// falls below the existential deposit, unless `keep_alive` is set, which fails instead.
+	/// Returns whether any funds moved, which they do not for a zero `amount` or from an account
+	/// to itself.
+	fn do_transfer(
+		&mut self,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+		keep_alive: bool,
+	) -> Result<bool, &'static str> {
+		if amount.is_zero() || from == to {
+			return Ok(false);
+		}
+		let from_balance = self.balance(from);
+		let to_balance = self.balance(to);
+
+		let new_from_balance = from_balance.checked_sub(&amount).ok_or("Not enough funds!")?;
+		if new_from_balance < self.locked(from) {
+			return Err("Not enough unlocked funds!");
+		}
+		let new_from_total =
+			self.reserved_balance(from).checked_add(&new_from_balance).ok_or("Overflow")?;
+		if keep_alive && new_from_total < T::EXISTENTIAL_DEPOSIT {
+			return Err("Transfer would reap the sending account.");
+		}
+		let new_to_balance = to_balance.checked_add(&amount).ok_or("Overflow")?;
+		let new_to_total =
+			self.reserved_balance(to).checked_add(&new_to_balance).ok_or("Overflow")?;
+		if new_to_total < T::EXISTENTIAL_DEPOSIT {
+			return Err("Balance is below the existential deposit.");
+		}
+
+		self.write_free(from, new_from_balance);
+		self.balances.insert(to.clone(), new_to_balance);
+		self.reap_if_dust(from)?;
+
+		Ok(true)
+	}
+
+	/// Write the free balance of `who`, without checking the existential deposit.
+	fn write_free(&mut self, who: &T::AccountId, amount: T::Balance) {
+		if amount.is_zero() {
+			self.balances.remove(who);
+		} else {
+			self.balances.insert(who.clone(), amount);
+		}
+	}
+
+	/// Write the reserved balance of `who`, without checking the existential deposit.
+	fn write_reserved(&mut self, who: &T::AccountId, amount: T::Balance) {
+		if amount.is_zero() {
+			self.reserved.remove(who);
+		} else {
+			self.reserved.insert(who.clone(), amount);
+		}
+	}
+
+	/// Add `amount` to the total issuance, for funds credited to some account out of nowhere.
+	/// Callers mint before writing any balance, so that nothing changes if the issuance
+	/// overflows.
+	fn mint_issuance(&mut self, amount: T::Balance) -> DispatchResult {
+		self.total_issuance = self.total_issuance.checked_add(&amount).ok_or("Overflow")?;
+		Ok(())
+	}
+
+	/// Remove `amount` from the total issuance, for funds removed from some account without being
+	/// credited to another. Handlers of such funds, like `DustRemoval`, mint them again if they
+	/// credit them to some account. Like `mint_issuance`, this is done before writing any balance.
+	fn burn_issuance(&mut self, amount: T::Balance) -> DispatchResult {
+		self.total_issuance = self.total_issuance.checked_sub(&amount).ok_or("Underflow")?;
+		Ok(())
+	}
+
+	/// Reap the account `who` if it is below the existential deposit, keeping what is left of its
+	/// balance as dust.
+	fn reap_if_dust(&mut self, who: &T::AccountId) -> DispatchResult {
+		let total = self.total_balance(who);
+		if total >= T::EXISTENTIAL_DEPOSIT {
+			return Ok(());
+		}
+		self.burn_issuance(total)?;
+		self.dust = self.dust.checked_add(&total).unwrap_or(self.dust);
+		self.reap_account(who.clone());
+		Ok(())
+	}
+
+	/// Remove the balances, the locks and the allowances from and to `who`, and remember to remove its
+	/// state in other pallets at the end of the block.
+	fn reap_account(&mut self, who: T::AccountId) {
+		self.locks.remove(&who);
+		self.allowances.retain(|(owner, spender), _| *owner != who && *spender != who);
+		let free = self.balances.remove(&who);
+		let reserved = self.reserved.remove(&who);
+		if free.is_some() || reserved.is_some() {
+			self.reaped.push(who);
+		}
+	}
+
+	/// Lock `amount` of the free balance of `who` under the identifier `id`, replacing any
+	/// previous lock with the same identifier. Locked funds stay in the free balance, but cannot be
+	/// transferred or reserved.
+	pub fn set_lock(&mut self, id: LockIdentifier, who: &T::AccountId, amount: T::Balance) {
+		if amount.is_zero() {
+			return self.remove_lock(id, who);
+		}
+		self.locks.entry(who.clone()).or_default().insert(id, amount);
+	}
+
+	/// Remove the lock with the identifier `id` on the balance of `who`.
+	pub fn remove_lock(&mut self, id: LockIdentifier, who: &T::AccountId) {
+		if let Some(locks) = self.locks.get_mut(who) {
+			locks.remove(&id);
+			if locks.is_empty() {
+				self.locks.remove(who);
+			}
+		}
+	}
+
+	/// Get the amount of the balance of `who` which is locked.
+	/// Locks overlap, so this is the largest of the locks on the account, not their sum.
+	pub fn locked(&self, who: &T::AccountId) -> T::Balance {
+		self.locks
+			.get(who)
+			.and_then(|locks| locks.values().max().copied())
+			.unwrap_or(T::Balance::zero())
+	}
 }
 
 #[macros::call]
 impl<T: Config> Pallet<T> {
 	/// Transfer `amount` from one account to another.
-	/// This function verifies that `from` has at least `amount` balance to transfer,
-	/// and that no mathematical overflows occur.
+	/// This function verifies that `from` has at least `amount` balance to transfer, without
+	/// touching its locked balance, and that no mathematical overflows occur. The receiving account
+	/// must end up with at least the existential deposit, while the sending account is reaped if
+	/// it falls below it.
 	pub fn transfer(
 		&mut self,
 		caller: T::AccountId,
 		to: T::AccountId,
 		amount: T::Balance,
-	) -> crate::support::DispatchResult {
-		let from_balance = self.balance(&caller);
-		let to_balance = self.balance(&to);
+	) -> DispatchResult {
+		if self.do_transfer(&caller, &to, amount, false)? {
+			self.deposit_event(Event::Transfer { from: caller, to, amount });
+		}
+		Ok(())
+	}
 
-		let new_from_balance = from_balance.checked_sub(&amount).ok_or("Not enough funds!")?;
-		let new_to_balance = to_balance.checked_add(&amount).ok_or("Overflow")?;
+	/// Transfer `amount` from one account to another, like `transfer`, but fail rather than reap
+	/// the sending account.
+	pub fn transfer_keep_alive(
+		&mut self,
+		caller: T::AccountId,
+		to: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if self.do_transfer(&caller, &to, amount, true)? {
+			self.deposit_event(Event::TransferKeepAlive { from: caller, to, amount });
+		}
+		Ok(())
+	}
+
+	/// Transfer all the funds of the caller which are not locked to `to`. With `keep_alive`, the
+	/// existential deposit is left in the account, so that it is not reaped.
+	pub fn transfer_all(
+		&mut self,
+		caller: T::AccountId,
+		to: T::AccountId,
+		keep_alive: bool,
+	) -> DispatchResult {
+		let mut keep = self.locked(&caller);
+		if keep_alive {
+			let reserved = self.reserved_balance(&caller);
+			let minimum =
+				T::EXISTENTIAL_DEPOSIT.checked_sub(&reserved).unwrap_or(T::Balance::zero());
+			keep = keep.max(minimum);
+		}
+		let amount = self.balance(&caller).checked_sub(&keep).unwrap_or(T::Balance::zero());
+		if self.do_transfer(&caller, &to, amount, keep_alive)? {
+			self.deposit_event(Event::TransferAll { from: caller, to, amount });
+		}
+		Ok(())
+	}
+
+	/// Transfer `amount` from `source` to `dest`, like `transfer`. Only root can move the funds
+	/// of another account.
+	pub fn force_transfer(
+		&mut self,
+		origin: OriginFor<T>,
+		source: T::AccountId,
+		dest: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		if self.do_transfer(&source, &dest, amount, false)? {
+			self.deposit_event(Event::ForceTransfer { from: source, to: dest, amount });
+		}
+		Ok(())
+	}
 
-		self.balances.insert(caller, new_from_balance);
-		self.balances.insert(to, new_to_balance);
+	/// Allow `spender` to transfer up to `amount` of the funds of the caller, replacing any
+	/// previous allowance. An `amount` of zero removes the allowance.
+	pub fn approve(
+		&mut self,
+		caller: T::AccountId,
+		spender: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let key = (caller.clone(), spender.clone());
+		if amount.is_zero() {
+			self.allowances.remove(&key);
+		} else {
+			self.allowances.insert(key, amount);
+		}
+		self.deposit_event(Event::Approval { owner: caller, spender, amount });
+		Ok(())
+	}
+
+	/// Transfer `amount` of the funds of `owner` to `to`, using the allowance of the caller. Like
+	/// `transfer_keep_alive`, this never reaps `owner`.
+	pub fn transfer_from(
+		&mut self,
+		caller: T::AccountId,
+		owner: T::AccountId,
+		to: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let remaining = self
+			.allowance(&owner, &caller)
+			.checked_sub(&amount)
+			.ok_or("Transfer exceeds the allowance.")?;
+		if !self.do_transfer(&owner, &to, amount, true)? {
+			return Ok(());
+		}
+
+		let key = (owner.clone(), caller.clone());
+		if remaining.is_zero() {
+			self.allowances.remove(&key);
+		} else {
+			self.allowances.insert(key, remaining);
+		}
+		self.deposit_event(Event::TransferFrom { owner, spender: caller, to, amount });
+		Ok(())
+	}
+
+	/// Create `amount` of new funds in the free balance of `who`. Only root can mint funds.
+	pub fn mint(
+		&mut self,
+		origin: OriginFor<T>,
+		who: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		self.deposit_creating(&who, amount)?;
+		self.deposit_event(Event::Minted { who, amount });
+		Ok(())
+	}
 
+	/// Destroy `amount` of the free balance of `who`, even if it is locked. The account is reaped
+	/// if it falls below the existential deposit. Only root can burn funds.
+	pub fn burn(
+		&mut self,
+		origin: OriginFor<T>,
+		who: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		let free = self.balance(&who).checked_sub(&amount).ok_or("Not enough funds!")?;
+		self.set_balance(who.clone(), free)?;
+		self.deposit_event(Event::Burned { who, amount });
 		Ok(())
 	}
+
+	/// Set the free balance of `who` to `free`, minting or burning the difference. Only root can
+	/// force the balance of an account.
+	pub fn force_set_balance(
+		&mut self,
+		origin: OriginFor<T>,
+		who: T::AccountId,
+		free: T::Balance,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		self.set_balance(who.clone(), free)?;
+		self.deposit_event(Event::BalanceSet { who, free });
+		Ok(())
+	}
+}
+
+impl<T> crate::support::Hooks<T, T::BlockNumber> for Pallet<T>
+where
+	T: Config + AsMut<crate::system::Pallet<T>> + AsMut<Pallet<T>>,
+{
+	/// Remove the nonces of the accounts reaped during the block, unless they were funded again
+	/// since, and hand their dust to `DustRemoval`.
+	fn on_finalize(runtime: &mut T, _n: T::BlockNumber) {
+		let balances: &mut Self = runtime.as_mut();
+		let reaped = core::mem::take(&mut balances.reaped);
+		let dust = core::mem::replace(&mut balances.dust, T::Balance::zero());
+		for who in reaped {
+			let balances: &mut Self = runtime.as_mut();
+			if !balances.total_balance(&who).is_zero() {
+				continue;
+			}
+			let system: &mut crate::system::Pallet<T> = runtime.as_mut();
+			system.kill_account(&who);
+		}
+		if !dust.is_zero() {
+			<T::DustRemoval as crate::support::OnUnbalanced<T, T::Balance>>::on_unbalanced(
+				runtime, dust,
+			);
+		}
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+impl<T> support::Currency<T, T::AccountId> for Pallet<T>
+where
+	T: Config + AsRef<Pallet<T>> + AsMut<Pallet<T>>,
+{
+	type Balance = T::Balance;
+
+	fn total_issuance(runtime: &T) -> T::Balance {
+		let balances: &Self = runtime.as_ref();
+		balances.total_issuance()
+	}
+
+	fn minimum_balance() -> T::Balance {
+		T::EXISTENTIAL_DEPOSIT
+	}
+
+	fn total_balance(runtime: &T, who: &T::AccountId) -> T::Balance {
+		let balances: &Self = runtime.as_ref();
+		balances.total_balance(who)
+	}
+
+	fn free_balance(runtime: &T, who: &T::AccountId) -> T::Balance {
+		let balances: &Self = runtime.as_ref();
+		balances.balance(who)
+	}
+
+	fn transfer(
+		runtime: &mut T,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let balances: &mut Self = runtime.as_mut();
+		balances.do_transfer(from, to, amount, false).map(|_| ())
+	}
+
+	fn withdraw(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let balances: &mut Self = runtime.as_mut();
+		balances.withdraw(who, amount)
+	}
+
+	fn slash(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let balances: &mut Self = runtime.as_mut();
+		balances.slash(who, amount)
+	}
+
+	fn deposit_creating(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let balances: &mut Self = runtime.as_mut();
+		balances.deposit_creating(who, amount)
+	}
+}
+
+impl<T> support::ReservableCurrency<T, T::AccountId> for Pallet<T>
+where
+	T: Config + AsRef<Pallet<T>> + AsMut<Pallet<T>>,
+{
+	fn reserved_balance(runtime: &T, who: &T::AccountId) -> T::Balance {
+		let balances: &Self = runtime.as_ref();
+		balances.reserved_balance(who)
+	}
+
+	fn reserve(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let balances: &mut Self = runtime.as_mut();
+		balances.reserve(who, amount)
+	}
+
+	fn unreserve(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let balances: &mut Self = runtime.as_mut();
+		balances.unreserve(who, amount)
+	}
+
+	fn slash_reserved(runtime: &mut T, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let balances: &mut Self = runtime.as_mut();
+		balances.slash_reserved(who, amount)
+	}
+
+	fn repatriate_reserved(
+		runtime: &mut T,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+		status: BalanceStatus,
+	) -> Result<T::Balance, &'static str> {
+		let balances: &mut Self = runtime.as_mut();
+		balances.repatriate_reserved(slashed, beneficiary, amount, status)
+	}
+}
+
+impl<T> support::LockableCurrency<T, T::AccountId> for Pallet<T>
+where
+	T: Config + AsRef<Pallet<T>> + AsMut<Pallet<T>>,
+{
+	fn set_lock(runtime: &mut T, id: LockIdentifier, who: &T::AccountId, amount: T::Balance) {
+		let balances: &mut Self = runtime.as_mut();
+		balances.set_lock(id, who, amount);
+	}
+
+	fn remove_lock(runtime: &mut T, id: LockIdentifier, who: &T::AccountId) {
+		let balances: &mut Self = runtime.as_mut();
+		balances.remove_lock(id, who);
+	}
 }
 
 #[cfg(test)]
@@ -63,6 +709,8 @@ mod tests {
 
 	impl super::Config for TestConfig {
 		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
 	}
 
 	impl crate::system::Config for TestConfig {
@@ -71,6 +719,8 @@ mod tests {
 		type BlockNumber = u32;
 
 		type Nonce = u32;
+
+		type RuntimeEvent = ();
 	}
 
 	#[test]
@@ -78,15 +728,17 @@ mod tests {
 		let mut balances = super::Pallet::<TestConfig>::new();
 
 		assert_eq!(balances.balance(&"Alice".to_string()), 0);
-		balances.set_balance("Alice".to_string(), 100);
+		balances.set_balance("Alice".to_string(), 100).unwrap();
 		assert_eq!(balances.balance(&"Alice".to_string()), 100);
 		assert_eq!(balances.balance(&"Bob".to_string()), 0);
 	}
 
 	#[test]
 	fn transfer_balance() {
+		use crate::support::PendingEvents;
+
 		let mut balances = super::Pallet::<TestConfig>::new();
-		balances.set_balance("Alice".to_string(), 100);
+		balances.set_balance("Alice".to_string(), 100).unwrap();
 
 		balances.transfer("Alice".to_string(), "Bob".to_string(), 50).unwrap();
 		assert_eq!(balances.balance(&"Alice".to_string()), 50);
@@ -96,5 +748,417 @@ mod tests {
 		assert_eq!(result, Err("Not enough funds!"));
 		assert_eq!(balances.balance(&"Alice".to_string()), 50);
 		assert_eq!(balances.balance(&"Bob".to_string()), 50);
+
+		// Transfers which move no funds emit no event.
+		balances.take_events();
+		balances.transfer("Alice".to_string(), "Bob".to_string(), 0).unwrap();
+		balances.transfer("Alice".to_string(), "Alice".to_string(), 10).unwrap();
+		assert_eq!(balances.balance(&"Alice".to_string()), 50);
+		assert!(balances.take_events().is_empty());
+	}
+
+	#[test]
+	fn transfer_keep_alive() {
+		use crate::support::PendingEvents;
+
+		let mut balances = super::Pallet::<TestConfig>::new();
+		let alice = "Alice".to_string();
+		let bob = "Bob".to_string();
+		balances.set_balance(alice.clone(), 100).unwrap();
+
+		let result = balances.transfer_keep_alive(alice.clone(), bob.clone(), 100);
+		assert_eq!(result, Err("Transfer would reap the sending account."));
+		balances.transfer_keep_alive(alice.clone(), bob.clone(), 99).unwrap();
+		assert_eq!(balances.balance(&alice), 1);
+		assert_eq!(balances.balance(&bob), 99);
+		assert!(matches!(
+			balances.take_events().as_slice(),
+			[super::Event::TransferKeepAlive { amount: 99, .. }]
+		));
+	}
+
+	#[test]
+	fn transfer_all() {
+		use crate::support::PendingEvents;
+
+		let mut balances = super::Pallet::<TestConfig>::new();
+		let alice = "Alice".to_string();
+		let bob = "Bob".to_string();
+		balances.set_balance(alice.clone(), 100).unwrap();
+
+		// Locked funds stay in the account.
+		balances.set_lock(*b"lock0000", &alice, 30);
+		balances.transfer_all(alice.clone(), bob.clone(), false).unwrap();
+		assert_eq!(balances.balance(&alice), 30);
+		assert_eq!(balances.balance(&bob), 70);
+		balances.remove_lock(*b"lock0000", &alice);
+
+		// Keeping the account alive leaves the existential deposit in it.
+		balances.transfer_all(alice.clone(), bob.clone(), true).unwrap();
+		assert_eq!(balances.balance(&alice), 1);
+		balances.transfer_all(alice.clone(), bob.clone(), false).unwrap();
+		assert_eq!(balances.balance(&alice), 0);
+		assert_eq!(balances.balance(&bob), 100);
+		assert!(matches!(
+			balances.take_events().as_slice(),
+			[
+				super::Event::TransferAll { amount: 70, .. },
+				super::Event::TransferAll { amount: 29, .. },
+				super::Event::TransferAll { amount: 1, .. },
+			]
+		));
+	}
+
+	#[test]
+	fn force_transfer() {
+		use crate::support::{PendingEvents, RawOrigin};
+
+		let mut balances = super::Pallet::<TestConfig>::new();
+		let alice = "Alice".to_string();
+		let bob = "Bob".to_string();
+		balances.set_balance(alice.clone(), 100).unwrap();
+
+		let signed = RawOrigin::Signed(bob.clone());
+		let result = balances.force_transfer(signed, alice.clone(), bob.clone(), 50);
+		assert_eq!(result, Err("Bad origin: expected the root origin."));
+		balances
+			.force_transfer(RawOrigin::Root, alice.clone(), bob.clone(), 50)
+			.unwrap();
+		assert_eq!(balances.balance(&alice), 50);
+		assert_eq!(balances.balance(&bob), 50);
+		assert!(matches!(
+			balances.take_events().as_slice(),
+			[super::Event::ForceTransfer { amount: 50, .. }]
+		));
+	}
+
+	#[test]
+	fn allowances() {
+		let mut balances = super::Pallet::<TestConfig>::new();
+		let alice = "Alice".to_string();
+		let bob = "Bob".to_string();
+		let charlie = "Charlie".to_string();
+		balances.set_balance(alice.clone(), 100).unwrap();
+
+		// Without an allowance, bob cannot move the funds of alice.
+		let result = balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 10);
+		assert_eq!(result, Err("Transfer exceeds the allowance."));
+
+		balances.approve(alice.clone(), bob.clone(), 50).unwrap();
+		assert_eq!(balances.allowance(&alice, &bob), 50);
+		balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 30).unwrap();
+		assert_eq!(balances.balance(&alice), 70);
+		assert_eq!(balances.balance(&charlie), 30);
+		assert_eq!(balances.allowance(&alice, &bob), 20);
+		let result = balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 30);
+		assert_eq!(result, Err("Transfer exceeds the allowance."));
+
+		// Approving again replaces the allowance, and the owner is never reaped.
+		balances.approve(alice.clone(), bob.clone(), 100).unwrap();
+		let result = balances.transfer_from(bob.clone(), alice.clone(), charlie.clone(), 70);
+		assert_eq!(result, Err("Transfer would reap the sending account."));
+		assert_eq!(balances.allowance(&alice, &bob), 100);
+		balances.approve(alice.clone(), bob.clone(), 0).unwrap();
+		assert_eq!(balances.allowance(&alice, &bob), 0);
+
+		// Reaping the spender removes its allowances, so that a new account with the same id does
+		// not inherit them.
+		balances.approve(alice.clone(), charlie.clone(), 10).unwrap();
+		balances.transfer(charlie.clone(), bob.clone(), 30).unwrap();
+		assert_eq!(balances.allowance(&alice, &charlie), 0);
+	}
+
+	#[test]
+	fn locked_balance() {
+		let mut balances = super::Pallet::<TestConfig>::new();
+		let alice = "Alice".to_string();
+		balances.set_balance(alice.clone(), 100).unwrap();
+
+		// Locks overlap, so only the largest one counts.
+		balances.set_lock(*b"lock0000", &alice, 30);
+		balances.set_lock(*b"lock0001", &alice, 60);
+		assert_eq!(balances.locked(&alice), 60);
+
+		let result = balances.transfer(alice.clone(), "Bob".to_string(), 50);
+		assert_eq!(result, Err("Not enough unlocked funds!"));
+		balances.transfer(alice.clone(), "Bob".to_string(), 40).unwrap();
+		assert_eq!(balances.balance(&alice), 60);
+
+		balances.remove_lock(*b"lock0001", &alice);
+		assert_eq!(balances.locked(&alice), 30);
+		balances.set_lock(*b"lock0000", &alice, 0);
+		assert_eq!(balances.locked(&alice), 0);
+		balances.transfer(alice.clone(), "Bob".to_string(), 60).unwrap();
+	}
+
+	#[test]
+	fn reserved_balance() {
+		use super::BalanceStatus;
+
+		let mut balances = super::Pallet::<TestConfig>::new();
+		let alice = "Alice".to_string();
+		let bob = "Bob".to_string();
+		balances.set_balance(alice.clone(), 100).unwrap();
+
+		balances.reserve(&alice, 30).unwrap();
+		assert_eq!(balances.balance(&alice), 70);
+		assert_eq!(balances.reserved_balance(&alice), 30);
+		assert_eq!(balances.total_balance(&alice), 100);
+		assert_eq!(balances.reserve(&alice, 80), Err("Not enough funds!"));
+		// Reserved funds can't be transferred, and locked funds can't be reserved.
+		let result = balances.transfer(alice.clone(), bob.clone(), 80);
+		assert_eq!(result, Err("Not enough funds!"));
+		balances.set_lock(*b"lock0000", &alice, 60);
+		assert_eq!(balances.reserve(&alice, 20), Err("Not enough unlocked funds!"));
+		balances.remove_lock(*b"lock0000", &alice);
+
+		// No more than is reserved is unreserved or slashed.
+		assert_eq!(balances.unreserve(&alice, 40), 30);
+		assert_eq!(balances.balance(&alice), 100);
+		balances.reserve(&alice, 50).unwrap();
+		assert_eq!(balances.slash_reserved(&alice, 20), 20);
+		assert_eq!(balances.total_balance(&alice), 80);
+
+		let repatriated = balances.repatriate_reserved(&alice, &bob, 100, BalanceStatus::Reserved);
+		assert_eq!(repatriated, Ok(30));
+		assert_eq!(balances.reserved_balance(&bob), 30);
+		assert_eq!(balances.balance(&bob), 0);
+		let repatriated = balances.repatriate_reserved(&bob, &alice, 10, BalanceStatus::Free);
+		assert_eq!(repatriated, Ok(10));
+		assert_eq!(balances.balance(&alice), 60);
+
+		// An account only holding reserved funds still exists, until they are slashed.
+		balances.reserve(&alice, 60).unwrap();
+		assert_eq!(balances.balance(&alice), 0);
+		assert_eq!(balances.total_balance(&alice), 60);
+		assert_eq!(balances.slash_reserved(&alice, 60), 60);
+		assert_eq!(balances.total_balance(&alice), 0);
+	}
+
+	#[test]
+	fn total_issuance() {
+		use crate::support::{PendingEvents, RawOrigin};
+
+		let mut balances = super::Pallet::<TestConfig>::new();
+		let alice = "Alice".to_string();
+		let bob = "Bob".to_string();
+		balances.set_balance(alice.clone(), 100).unwrap();
+		balances.set_balance(bob.clone(), 50).unwrap();
+		assert_eq!(balances.total_issuance(), 150);
+
+		// Nothing changes if the issuance would overflow.
+		assert_eq!(balances.set_balance(bob.clone(), u32::MAX), Err("Overflow"));
+		let result = balances.mint(RawOrigin::Root, alice.clone(), u32::MAX - 100);
+		assert_eq!(result, Err("Overflow"));
+		assert_eq!(balances.balance(&alice), 100);
+		assert_eq!(balances.balance(&bob), 50);
+		assert_eq!(balances.total_issuance(), 150);
+
+		// Transfers and reserves move funds around without changing the issuance, while slashes
+		// burn them.
+		balances.transfer(alice.clone(), bob.clone(), 30).unwrap();
+		balances.reserve(&bob, 40).unwrap();
+		assert_eq!(balances.total_issuance(), 150);
+		balances.slash_reserved(&bob, 10);
+		assert_eq!(balances.total_issuance(), 140);
+		balances.check_issuance().unwrap();
+
+		// Only root can mint and burn funds.
+		let signed = RawOrigin::Signed(alice.clone());
+		assert_eq!(
+			balances.mint(signed, alice.clone(), 10),
+			Err("Bad origin: expected the root origin.")
+		);
+		balances.mint(RawOrigin::Root, alice.clone(), 10).unwrap();
+		assert_eq!(balances.balance(&alice), 80);
+		assert_eq!(balances.total_issuance(), 150);
+		let result = balances.burn(RawOrigin::Root, alice.clone(), 90);
+		assert_eq!(result, Err("Not enough funds!"));
+		balances.burn(RawOrigin::Root, alice.clone(), 20).unwrap();
+		assert_eq!(balances.total_issuance(), 130);
+		balances.force_set_balance(RawOrigin::Root, bob.clone(), 0).unwrap();
+		assert_eq!(balances.total_issuance(), 90);
+		assert_eq!(balances.total_balance(&bob), 30);
+		balances.check_issuance().unwrap();
+		assert!(matches!(
+			balances.take_events().as_slice(),
+			[
+				super::Event::Transfer { .. },
+				super::Event::Minted { amount: 10, .. },
+				super::Event::Burned { amount: 20, .. },
+				super::Event::BalanceSet { free: 0, .. },
+			]
+		));
+
+		// Withdrawn funds are burned, unlike locked ones, and deposited funds are minted.
+		balances.set_lock(*b"lock0000", &alice, 50);
+		assert_eq!(balances.withdraw(&alice, 20), Err("Not enough unlocked funds!"));
+		balances.withdraw(&alice, 10).unwrap();
+		balances.deposit_creating(&bob, 5).unwrap();
+		assert_eq!(balances.total_issuance(), 85);
+		balances.check_issuance().unwrap();
+	}
+
+	mod reaping {
+		use crate::{
+			balances,
+			support::{InherentData, OnUnbalanced},
+		};
+
+		#[derive(Debug, Clone)]
+		#[macros::runtime]
+		struct Runtime {
+			system: crate::system::Pallet<Self>,
+			#[hooks]
+			balances: balances::Pallet<Self>,
+		}
+
+		impl crate::system::Config for Runtime {
+			type AccountId = &'static str;
+			type BlockNumber = u32;
+			type Nonce = u32;
+			type RuntimeEvent = RuntimeEvent;
+		}
+
+		/// Collects the dust in the account "dust", which must exist already.
+		struct CollectDust;
+
+		impl OnUnbalanced<Runtime, u32> for CollectDust {
+			fn on_unbalanced(runtime: &mut Runtime, amount: u32) {
+				let balance = runtime.balances.balance(&"dust");
+				runtime.balances.set_balance("dust", balance + amount).unwrap();
+			}
+		}
+
+		impl balances::Config for Runtime {
+			type Balance = u32;
+			type DustRemoval = CollectDust;
+			const EXISTENTIAL_DEPOSIT: u32 = 10;
+		}
+
+		#[test]
+		fn accounts_below_existential_deposit_are_reaped() {
+			let mut runtime = Runtime::new();
+			runtime.balances.set_balance("alice", 100).unwrap();
+			runtime.balances.set_balance("dust", 100).unwrap();
+			runtime.system.inc_nonce("alice");
+			runtime.system.inc_nonce("bob");
+
+			// New accounts must receive at least the existential deposit.
+			let result = runtime.balances.transfer("alice", "bob", 5);
+			assert_eq!(result, Err("Balance is below the existential deposit."));
+			runtime.balances.transfer("alice", "bob", 50).unwrap();
+
+			// Alice falls below the existential deposit, so her account is reaped and the rest of
+			// her balance is dust.
+			runtime.balances.transfer("alice", "bob", 45).unwrap();
+			assert_eq!(runtime.balances.balance(&"alice"), 0);
+			assert_eq!(runtime.balances.balance(&"bob"), 95);
+
+			// The nonces and the dust are handled at the end of the block.
+			assert!(runtime.system.nonce.contains_key("alice"));
+			let block = runtime.build_block(&InherentData::new(), vec![]);
+			runtime.execute_block(block).unwrap();
+			assert!(!runtime.system.nonce.contains_key("alice"));
+			assert!(runtime.system.nonce.contains_key("bob"));
+			assert_eq!(runtime.balances.balance(&"dust"), 105);
+
+			// An account funded again before the end of the block keeps its nonce.
+			runtime.balances.transfer("bob", "charlie", 90).unwrap();
+			runtime.balances.transfer("charlie", "bob", 20).unwrap();
+			let block = runtime.build_block(&InherentData::new(), vec![]);
+			runtime.execute_block(block).unwrap();
+			assert!(runtime.system.nonce.contains_key("bob"));
+			assert_eq!(runtime.balances.balance(&"dust"), 110);
+
+			// The dust handed to `DustRemoval` is minted again when credited to an account.
+			assert_eq!(runtime.balances.total_issuance(), 200);
+			runtime.balances.check_issuance().unwrap();
+		}
+	}
+
+	mod currency {
+		use crate::{
+			balances,
+			support::{BalanceStatus, LockableCurrency, PendingEvents, ReservableCurrency},
+		};
+
+		#[derive(Debug, Clone)]
+		#[macros::runtime]
+		struct Runtime {
+			system: crate::system::Pallet<Self>,
+			#[event]
+			balances: balances::Pallet<Self>,
+		}
+
+		impl crate::system::Config for Runtime {
+			type AccountId = &'static str;
+			type BlockNumber = u32;
+			type Nonce = u32;
+			type RuntimeEvent = RuntimeEvent;
+		}
+
+		impl balances::Config for Runtime {
+			type Balance = u32;
+			type DustRemoval = ();
+			const EXISTENTIAL_DEPOSIT: u32 = 10;
+		}
+
+		/// Move the funds of the runtime only through the currency traits, as pallets do.
+		fn move_funds<C>()
+		where
+			C: ReservableCurrency<Runtime, &'static str, Balance = u32>
+				+ LockableCurrency<Runtime, &'static str>,
+		{
+			let mut runtime = Runtime::new();
+			runtime.balances.set_balance("alice", 100).unwrap();
+			assert_eq!(C::minimum_balance(), 10);
+			assert_eq!(C::total_issuance(&runtime), 100);
+
+			// Reserved funds stay in the account, but cannot be transferred.
+			C::reserve(&mut runtime, &"alice", 30).unwrap();
+			assert_eq!(C::free_balance(&runtime, &"alice"), 70);
+			assert_eq!(C::reserved_balance(&runtime, &"alice"), 30);
+			assert_eq!(C::total_balance(&runtime, &"alice"), 100);
+			assert_eq!(C::transfer(&mut runtime, &"alice", &"bob", 80), Err("Not enough funds!"));
+			assert_eq!(C::unreserve(&mut runtime, &"alice", 10), 10);
+
+			// Repatriated funds end up free or reserved in the beneficiary account.
+			let result =
+				C::repatriate_reserved(&mut runtime, &"alice", &"bob", 15, BalanceStatus::Free);
+			assert_eq!(result, Ok(15));
+			let result =
+				C::repatriate_reserved(&mut runtime, &"alice", &"bob", 50, BalanceStatus::Reserved);
+			assert_eq!(result, Ok(5));
+			assert_eq!(C::reserved_balance(&runtime, &"alice"), 0);
+			assert_eq!(C::free_balance(&runtime, &"bob"), 15);
+			assert_eq!(C::reserved_balance(&runtime, &"bob"), 5);
+
+			// Locked funds can neither be transferred nor withdrawn, but they can be slashed.
+			C::set_lock(&mut runtime, *b"lock0000", &"alice", 60);
+			let result = C::transfer(&mut runtime, &"alice", &"bob", 30);
+			assert_eq!(result, Err("Not enough unlocked funds!"));
+			assert_eq!(C::withdraw(&mut runtime, &"alice", 30), Err("Not enough unlocked funds!"));
+			C::withdraw(&mut runtime, &"alice", 20).unwrap();
+			assert_eq!(C::slash(&mut runtime, &"alice", 20), 20);
+			C::remove_lock(&mut runtime, *b"lock0000", &"alice");
+			C::transfer(&mut runtime, &"alice", &"bob", 30).unwrap();
+			assert_eq!(C::free_balance(&runtime, &"alice"), 10);
+			assert_eq!(C::free_balance(&runtime, &"bob"), 45);
+
+			// Withdrawn and slashed funds are burned, and deposited funds are minted.
+			assert_eq!(C::total_issuance(&runtime), 60);
+			C::deposit_creating(&mut runtime, &"charlie", 10).unwrap();
+			assert_eq!(C::total_issuance(&runtime), 70);
+			runtime.balances.check_issuance().unwrap();
+
+			// Unlike the calls of the pallet, the currency emits no event.
+			assert!(runtime.balances.take_events().is_empty());
+		}
+
+		#[test]
+		fn balances_is_a_currency() {
+			move_funds::<balances::Pallet<Runtime>>();
+		}
 	}
 }
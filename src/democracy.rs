@@ -0,0 +1,679 @@
+use std::collections::BTreeMap;
+
+use num::{CheckedAdd, CheckedMul, CheckedSub, Zero};
+
+use crate::{
+	support::{
+		self, CallOf, Currency, DispatchResult, LockIdentifier, LockableCurrency, RawOrigin,
+		ReservableCurrency,
+	},
+	system::OriginFor,
+};
+
+/// The identifier of the lock the Democracy Module places on the funds voting.
+pub const DEMOCRACY_ID: LockIdentifier = *b"democrac";
+
+/// The index of a public proposal.
+pub type PropIndex = u32;
+/// The index of a referendum.
+pub type ReferendumIndex = u32;
+
+/// The type of the deposits and votes of the referenda.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config:
+	Sized
+	+ crate::system::Config<BlockNumber: From<u32> + CheckedMul>
+	+ crate::scheduler::Config
+	+ AsMut<Pallet<Self>>
+{
+	/// The currency the deposits are reserved in, and the votes are locked in.
+	type Currency: ReservableCurrency<Self, Self::AccountId, Balance: Into<u128>>
+		+ LockableCurrency<Self, Self::AccountId>;
+	/// The minimum deposit of a proposal.
+	const MINIMUM_DEPOSIT: BalanceOf<Self>;
+	/// The number of blocks between the launch of two referenda.
+	const LAUNCH_PERIOD: Self::BlockNumber;
+	/// The number of blocks a referendum can be voted on for.
+	const VOTING_PERIOD: Self::BlockNumber;
+	/// The number of blocks between the approval of a referendum and its enactment. Must not be
+	/// zero.
+	const ENACTMENT_PERIOD: Self::BlockNumber;
+	/// The number of blocks the funds of a vote with `Locked1x` conviction stay locked for after
+	/// the end of the referendum. Higher convictions lock the funds for longer.
+	const VOTE_LOCKING_PERIOD: Self::BlockNumber;
+}
+
+/// How long a voter is willing to lock their funds for after a referendum, in exchange for more
+/// voting power.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Conviction {
+	/// 0.1x votes, no lock after the referendum.
+	None,
+	/// 1x votes, locked for one locking period.
+	Locked1x,
+	/// 2x votes, locked for two locking periods.
+	Locked2x,
+	/// 3x votes, locked for four locking periods.
+	Locked3x,
+	/// 4x votes, locked for eight locking periods.
+	Locked4x,
+	/// 5x votes, locked for sixteen locking periods.
+	Locked5x,
+	/// 6x votes, locked for thirty-two locking periods.
+	Locked6x,
+}
+
+impl Conviction {
+	/// The number of votes of `balance` with this conviction.
+	pub fn votes(self, balance: u128) -> u128 {
+		match self {
+			Conviction::None => balance / 10,
+			Conviction::Locked1x => balance,
+			Conviction::Locked2x => balance.saturating_mul(2),
+			Conviction::Locked3x => balance.saturating_mul(3),
+			Conviction::Locked4x => balance.saturating_mul(4),
+			Conviction::Locked5x => balance.saturating_mul(5),
+			Conviction::Locked6x => balance.saturating_mul(6),
+		}
+	}
+
+	/// The number of locking periods the funds of a vote stay locked for after the referendum.
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 4,
+			Conviction::Locked4x => 8,
+			Conviction::Locked5x => 16,
+			Conviction::Locked6x => 32,
+		}
+	}
+}
+
+/// A vote for or against a referendum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vote {
+	/// Whether the vote is in favour of the referendum.
+	pub aye: bool,
+	/// The conviction of the vote.
+	pub conviction: Conviction,
+}
+
+/// The votes counted for and against a referendum.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tally {
+	/// The votes in favour of the referendum.
+	pub ayes: u128,
+	/// The votes against the referendum.
+	pub nays: u128,
+}
+
+/// The events emitted by the Democracy Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// `proposer` submitted the proposal `index`, with a `deposit`.
+	Proposed { index: PropIndex, proposer: T::AccountId, deposit: BalanceOf<T> },
+	/// `who` seconded the proposal `index`.
+	Seconded { index: PropIndex, who: T::AccountId },
+	/// The proposal `proposal` became the referendum `index`.
+	Started { index: ReferendumIndex, proposal: PropIndex },
+	/// `voter` voted on the referendum `index` with `balance`.
+	Voted { voter: T::AccountId, index: ReferendumIndex, vote: Vote, balance: BalanceOf<T> },
+	/// The referendum `index` passed, and will be enacted with the root origin.
+	Passed { index: ReferendumIndex },
+	/// The enactment of the referendum `index`, which passed, could not be scheduled.
+	EnactmentFailed { index: ReferendumIndex, error: &'static str },
+	/// The referendum `index` did not pass.
+	NotPassed { index: ReferendumIndex },
+	/// `amount` of the funds of `who` locked by their votes were unlocked.
+	Unlocked { who: T::AccountId, amount: BalanceOf<T> },
+}
+
+/// A public proposal waiting to become a referendum.
+#[derive(Debug, Clone)]
+pub struct Proposal<T: Config> {
+	/// The index of the proposal.
+	pub index: PropIndex,
+	/// The call dispatched with the root origin if the referendum passes.
+	pub call: CallOf<T>,
+	/// The deposits of the proposer and of each account which seconded the proposal.
+	pub deposits: Vec<(T::AccountId, BalanceOf<T>)>,
+}
+
+impl<T: Config> Proposal<T> {
+	/// The total deposit backing the proposal, which orders the proposals to launch.
+	fn backing(&self) -> u128 {
+		self.deposits
+			.iter()
+			.fold(0, |backing: u128, (_, deposit)| backing.saturating_add((*deposit).into()))
+	}
+}
+
+/// The state of a referendum.
+#[derive(Debug, Clone)]
+pub enum ReferendumInfo<T: Config> {
+	/// The referendum can be voted on until block `end`.
+	Ongoing { end: T::BlockNumber, call: CallOf<T>, tally: Tally },
+	/// The referendum ended at block `end`.
+	Finished { end: T::BlockNumber, approved: bool },
+}
+
+/// A vote of an account on a referendum, and the funds it locks.
+#[derive(Debug, Clone)]
+pub struct VoteRecord<T: Config> {
+	/// The referendum voted on.
+	pub referendum: ReferendumIndex,
+	/// The vote itself.
+	pub vote: Vote,
+	/// The funds voting, which are locked until some time after the end of the referendum.
+	pub balance: BalanceOf<T>,
+}
+
+/// This is the Democracy Module.
+/// It allows any account to propose a call, to be dispatched with the root origin if token holders
+/// approve it. The most backed proposal regularly becomes a referendum, which token holders vote on
+/// with their balance. A referendum which passes is enacted after a delay, through the scheduler.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The proposals waiting to become a referendum.
+	proposals: Vec<Proposal<T>>,
+	/// The index of the next proposal.
+	next_proposal: PropIndex,
+	/// The referenda, ongoing or finished.
+	referenda: BTreeMap<ReferendumIndex, ReferendumInfo<T>>,
+	/// The index of the next referendum.
+	next_referendum: ReferendumIndex,
+	/// The block at which the next referendum is launched.
+	next_launch: T::BlockNumber,
+	/// The votes of each account.
+	votes: BTreeMap<T::AccountId, Vec<VoteRecord<T>>>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Democracy Module.
+	pub fn new() -> Self {
+		Self {
+			proposals: Vec::new(),
+			next_proposal: 0,
+			referenda: BTreeMap::new(),
+			next_referendum: 0,
+			next_launch: T::LAUNCH_PERIOD,
+			votes: BTreeMap::new(),
+			events: Vec::new(),
+		}
+	}
+
+	/// Get the proposals waiting to become a referendum.
+	pub fn proposals(&self) -> &[Proposal<T>] {
+		&self.proposals
+	}
+
+	/// Get the referendum `index`.
+	pub fn referendum(&self, index: ReferendumIndex) -> Option<&ReferendumInfo<T>> {
+		self.referenda.get(&index)
+	}
+
+	/// Get the votes of `who`.
+	pub fn votes(&self, who: &T::AccountId) -> &[VoteRecord<T>] {
+		self.votes.get(who).map(|votes| votes.as_slice()).unwrap_or(&[])
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// The largest balance `votes` lock. The same funds can vote on several referenda at once.
+	fn locked_by(votes: &[VoteRecord<T>]) -> BalanceOf<T> {
+		votes
+			.iter()
+			.map(|record| record.balance)
+			.max()
+			.unwrap_or_else(BalanceOf::<T>::zero)
+	}
+
+	/// Update the lock on the funds of `who` to cover the votes they have not unlocked yet.
+	fn update_lock(runtime: &mut T, who: &T::AccountId) {
+		let democracy: &mut Self = runtime.as_mut();
+		let locked = Self::locked_by(democracy.votes(who));
+		<T as Config>::Currency::set_lock(runtime, DEMOCRACY_ID, who, locked);
+	}
+
+	/// Turn the most backed proposal into a referendum ending at `end`, returning the deposits
+	/// of the proposal.
+	fn launch_referendum(runtime: &mut T, end: T::BlockNumber) {
+		let democracy: &mut Self = runtime.as_mut();
+		let Some((position, _)) =
+			democracy.proposals.iter().enumerate().max_by_key(|(position, proposal)| {
+				(proposal.backing(), core::cmp::Reverse(*position))
+			})
+		else {
+			return;
+		};
+		let proposal = democracy.proposals.remove(position);
+
+		let index = democracy.next_referendum;
+		democracy.next_referendum += 1;
+		let tally = Tally::default();
+		democracy
+			.referenda
+			.insert(index, ReferendumInfo::Ongoing { end, call: proposal.call, tally });
+		democracy.deposit_event(Event::Started { index, proposal: proposal.index });
+
+		for (who, deposit) in proposal.deposits {
+			<T as Config>::Currency::unreserve(runtime, &who, deposit);
+		}
+	}
+
+	/// Count the votes of the referenda ending at block `now`, and schedule the enactment of the
+	/// ones which passed.
+	fn finish_referenda(runtime: &mut T, now: T::BlockNumber) {
+		let democracy: &mut Self = runtime.as_mut();
+		let ending = democracy
+			.referenda
+			.iter()
+			.filter(|(_, info)| matches!(info, ReferendumInfo::Ongoing { end, .. } if *end <= now))
+			.map(|(index, _)| *index)
+			.collect::<Vec<_>>();
+
+		for index in ending {
+			let democracy: &mut Self = runtime.as_mut();
+			let Some(ReferendumInfo::Ongoing { end, call, tally }) =
+				democracy.referenda.remove(&index)
+			else {
+				continue;
+			};
+			let approved = tally.ayes > tally.nays;
+			democracy.referenda.insert(index, ReferendumInfo::Finished { end, approved });
+			if !approved {
+				democracy.deposit_event(Event::NotPassed { index });
+				continue;
+			}
+			democracy.deposit_event(Event::Passed { index });
+
+			let result = now.checked_add(&T::ENACTMENT_PERIOD).ok_or("Overflow").and_then(|when| {
+				crate::scheduler::Pallet::<T>::do_schedule(
+					runtime,
+					RawOrigin::Root,
+					None,
+					when,
+					None,
+					call,
+				)
+			});
+			if let Err(error) = result {
+				let democracy: &mut Self = runtime.as_mut();
+				democracy.deposit_event(Event::EnactmentFailed { index, error });
+			}
+		}
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Propose `proposal` to be dispatched with the root origin, reserving `value` from the
+	/// caller as a deposit until the proposal becomes a referendum.
+	pub fn propose(
+		runtime: &mut T,
+		caller: T::AccountId,
+		proposal: Box<CallOf<T>>,
+		value: BalanceOf<T>,
+	) -> DispatchResult {
+		if value < T::MINIMUM_DEPOSIT {
+			return Err("The deposit is less than the minimum deposit.");
+		}
+		<T as Config>::Currency::reserve(runtime, &caller, value)?;
+
+		let democracy: &mut Self = runtime.as_mut();
+		let index = democracy.next_proposal;
+		democracy.next_proposal += 1;
+		democracy.proposals.push(Proposal {
+			index,
+			call: *proposal,
+			deposits: vec![(caller.clone(), value)],
+		});
+		democracy.deposit_event(Event::Proposed { index, proposer: caller, deposit: value });
+		Ok(())
+	}
+
+	/// Second the proposal `proposal`, reserving the same deposit as its proposer from the caller,
+	/// which makes it more likely to become the next referendum.
+	pub fn second(runtime: &mut T, caller: T::AccountId, proposal: PropIndex) -> DispatchResult {
+		let democracy: &mut Self = runtime.as_mut();
+		let deposit = democracy
+			.proposals
+			.iter()
+			.find(|candidate| candidate.index == proposal)
+			.ok_or("Proposal does not exist.")?
+			.deposits[0]
+			.1;
+
+		<T as Config>::Currency::reserve(runtime, &caller, deposit)?;
+
+		let democracy: &mut Self = runtime.as_mut();
+		let candidate = democracy
+			.proposals
+			.iter_mut()
+			.find(|candidate| candidate.index == proposal)
+			.expect("The proposal exists.");
+		candidate.deposits.push((caller.clone(), deposit));
+		democracy.deposit_event(Event::Seconded { index: proposal, who: caller });
+		Ok(())
+	}
+
+	/// Vote on the ongoing referendum `referendum` with `balance` of the funds of the caller,
+	/// which are locked until the referendum ends, and longer with a conviction.
+	pub fn vote(
+		runtime: &mut T,
+		caller: T::AccountId,
+		referendum: ReferendumIndex,
+		vote: Vote,
+		balance: BalanceOf<T>,
+	) -> DispatchResult {
+		if balance.is_zero() {
+			return Err("Cannot vote with no funds.");
+		}
+		let democracy: &mut Self = runtime.as_mut();
+		match democracy.referenda.get(&referendum) {
+			Some(ReferendumInfo::Ongoing { .. }) => {},
+			_ => return Err("Referendum is not ongoing."),
+		}
+		if democracy.votes(&caller).iter().any(|record| record.referendum == referendum) {
+			return Err("Already voted.");
+		}
+
+		if <T as Config>::Currency::free_balance(runtime, &caller) < balance {
+			return Err("Not enough funds!");
+		}
+
+		let democracy: &mut Self = runtime.as_mut();
+		if let Some(ReferendumInfo::Ongoing { tally, .. }) =
+			democracy.referenda.get_mut(&referendum)
+		{
+			let votes = vote.conviction.votes(balance.into());
+			if vote.aye {
+				tally.ayes = tally.ayes.saturating_add(votes);
+			} else {
+				tally.nays = tally.nays.saturating_add(votes);
+			}
+		}
+		democracy.votes.entry(caller.clone()).or_default().push(VoteRecord {
+			referendum,
+			vote,
+			balance,
+		});
+		democracy.deposit_event(Event::Voted {
+			voter: caller.clone(),
+			index: referendum,
+			vote,
+			balance,
+		});
+		Self::update_lock(runtime, &caller);
+		Ok(())
+	}
+
+	/// Unlock the funds of the caller locked by votes on referenda which are done locking them.
+	pub fn unlock(runtime: &mut T, caller: T::AccountId) -> DispatchResult {
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		let now = system.block_number();
+
+		let democracy: &mut Self = runtime.as_mut();
+		let votes = democracy.votes.remove(&caller).unwrap_or_default();
+		let before = Self::locked_by(&votes);
+		let (_, locked): (Vec<_>, Vec<_>) = votes.into_iter().partition(|record| {
+			match democracy.referenda.get(&record.referendum) {
+				Some(ReferendumInfo::Finished { end, .. }) => {
+					let periods = T::BlockNumber::from(record.vote.conviction.lock_periods());
+					T::VOTE_LOCKING_PERIOD
+						.checked_mul(&periods)
+						.and_then(|lock| end.checked_add(&lock))
+						.is_some_and(|unlock_at| unlock_at <= now)
+				},
+				_ => false,
+			}
+		});
+		let amount = before.checked_sub(&Self::locked_by(&locked)).ok_or("Underflow")?;
+		if !locked.is_empty() {
+			democracy.votes.insert(caller.clone(), locked);
+		}
+		Self::update_lock(runtime, &caller);
+
+		let democracy: &mut Self = runtime.as_mut();
+		democracy.deposit_event(Event::Unlocked { who: caller, amount });
+		Ok(())
+	}
+
+	/// Cancel the referendum `referendum`, which then never passes. Only root can cancel it.
+	pub fn cancel_referendum(
+		&mut self,
+		origin: OriginFor<T>,
+		referendum: ReferendumIndex,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		match self.referenda.get(&referendum) {
+			Some(ReferendumInfo::Ongoing { end, .. }) => {
+				let end = *end;
+				self.referenda
+					.insert(referendum, ReferendumInfo::Finished { end, approved: false });
+				self.deposit_event(Event::NotPassed { index: referendum });
+				Ok(())
+			},
+			_ => Err("Referendum is not ongoing."),
+		}
+	}
+}
+
+impl<T: Config> support::Hooks<T, T::BlockNumber> for Pallet<T> {
+	/// Launch a new referendum every `LAUNCH_PERIOD` blocks, and count the votes of the referenda
+	/// which end.
+	fn on_initialize(runtime: &mut T, now: T::BlockNumber) {
+		Self::finish_referenda(runtime, now);
+
+		let democracy: &mut Self = runtime.as_mut();
+		if now >= democracy.next_launch {
+			democracy.next_launch = now.checked_add(&T::LAUNCH_PERIOD).unwrap_or(now);
+			let end = now.checked_add(&T::VOTING_PERIOD).unwrap_or(now);
+			Self::launch_referendum(runtime, end);
+		}
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Conviction, Event, ReferendumInfo, Tally, Vote};
+	use crate::{
+		balances, democracy, scheduler,
+		support::{Dispatch, RawOrigin, Weight},
+		testing::account,
+	};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[hooks]
+		scheduler: scheduler::Pallet<Self>,
+		#[hooks]
+		#[event]
+		democracy: democracy::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = String;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl scheduler::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		type TaskName = &'static str;
+		const MAXIMUM_WEIGHT: Weight = 100;
+		const TASK_WEIGHT: Weight = 10;
+		const MAX_SCHEDULED_PER_BLOCK: u32 = 10;
+		const TASK_DEPOSIT: u32 = 5;
+	}
+
+	impl democracy::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		const MINIMUM_DEPOSIT: u32 = 10;
+		const LAUNCH_PERIOD: u32 = 2;
+		const VOTING_PERIOD: u32 = 2;
+		const ENACTMENT_PERIOD: u32 = 1;
+		const VOTE_LOCKING_PERIOD: u32 = 2;
+	}
+
+	crate::testing::impl_run_to_block!();
+
+	crate::testing::impl_dispatch!(democracy);
+
+	// A call which only root can make, which schedules a task named `name`.
+	fn root_call(name: &'static str) -> Box<RuntimeCall> {
+		let transfer =
+			RuntimeCall::balances(balances::Call::transfer { to: account("bob"), amount: 1 });
+		Box::new(RuntimeCall::scheduler(scheduler::Call::schedule_named {
+			name,
+			when: 100,
+			maybe_periodic: None,
+			call: Box::new(transfer),
+		}))
+	}
+
+	fn vote(aye: bool, conviction: Conviction, balance: u32) -> democracy::Call<Runtime> {
+		democracy::Call::vote { referendum: 0, vote: Vote { aye, conviction }, balance }
+	}
+
+	#[test]
+	fn referendum_is_enacted() {
+		let mut runtime = Runtime::new();
+		for who in ["alice", "bob", "charlie"] {
+			runtime.balances.set_balance(account(who), 100).unwrap();
+		}
+		run_to_block(&mut runtime, 1);
+
+		let propose = democracy::Call::propose { proposal: root_call("enacted"), value: 10 };
+		dispatch(&mut runtime, "alice", propose).unwrap();
+		dispatch(&mut runtime, "bob", democracy::Call::second { proposal: 0 }).unwrap();
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 10);
+		assert_eq!(runtime.balances.reserved_balance(&account("bob")), 10);
+
+		// The proposal becomes a referendum, and its deposits are returned.
+		run_to_block(&mut runtime, 2);
+		assert!(runtime.democracy.proposals().is_empty());
+		assert!(matches!(
+			runtime.democracy.referendum(0),
+			Some(ReferendumInfo::Ongoing { end: 4, .. })
+		));
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 0);
+		assert_eq!(runtime.balances.balance(&account("bob")), 100);
+
+		// Conviction multiplies the votes of alice, and divides those of bob.
+		dispatch(&mut runtime, "alice", vote(true, Conviction::Locked1x, 30)).unwrap();
+		dispatch(&mut runtime, "bob", vote(false, Conviction::None, 100)).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, "bob", vote(true, Conviction::None, 10)),
+			Err("Already voted.")
+		);
+		assert_eq!(runtime.balances.balance(&account("alice")), 100);
+		assert_eq!(runtime.balances.locked(&account("alice")), 30);
+		assert!(matches!(
+			runtime.democracy.referendum(0),
+			Some(ReferendumInfo::Ongoing { tally: Tally { ayes: 30, nays: 10 }, .. })
+		));
+
+		// The referendum passes, and is enacted with the root origin after the enactment period.
+		run_to_block(&mut runtime, 4);
+		assert!(matches!(
+			runtime.system.events(),
+			[RuntimeEvent::democracy(Event::Passed { index: 0 })]
+		));
+		assert!(runtime.scheduler.lookup(&"enacted").is_none());
+		run_to_block(&mut runtime, 5);
+		let (when, index) = *runtime.scheduler.lookup(&"enacted").unwrap();
+		let task = runtime.scheduler.agenda(&when)[index as usize].as_ref().unwrap();
+		assert_eq!(task.origin, RawOrigin::Root);
+
+		// Without conviction, the funds of bob are unlocked as soon as the referendum ends, while
+		// the funds of alice stay locked for the locking period.
+		dispatch(&mut runtime, "bob", democracy::Call::unlock {}).unwrap();
+		dispatch(&mut runtime, "alice", democracy::Call::unlock {}).unwrap();
+		assert_eq!(runtime.balances.locked(&account("bob")), 0);
+		assert_eq!(runtime.balances.locked(&account("alice")), 30);
+		run_to_block(&mut runtime, 6);
+		dispatch(&mut runtime, "alice", democracy::Call::unlock {}).unwrap();
+		assert_eq!(runtime.balances.locked(&account("alice")), 0);
+		assert!(runtime.democracy.votes(&account("alice")).is_empty());
+	}
+
+	#[test]
+	fn referendum_is_rejected() {
+		let mut runtime = Runtime::new();
+		for who in ["alice", "bob", "charlie"] {
+			runtime.balances.set_balance(account(who), 100).unwrap();
+		}
+
+		let propose = |name, value| democracy::Call::propose { proposal: root_call(name), value };
+		assert_eq!(
+			dispatch(&mut runtime, "alice", propose("first", 5)),
+			Err("The deposit is less than the minimum deposit.")
+		);
+		dispatch(&mut runtime, "alice", propose("first", 10)).unwrap();
+		dispatch(&mut runtime, "bob", propose("second", 20)).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, "charlie", democracy::Call::second { proposal: 2 }),
+			Err("Proposal does not exist.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "charlie", vote(true, Conviction::None, 10)),
+			Err("Referendum is not ongoing.")
+		);
+
+		// The most backed proposal becomes a referendum first.
+		run_to_block(&mut runtime, 2);
+		assert!(matches!(
+			runtime.system.events(),
+			[RuntimeEvent::democracy(Event::Started { index: 0, proposal: 1 })]
+		));
+		assert_eq!(runtime.democracy.proposals()[0].index, 0);
+
+		dispatch(&mut runtime, "alice", vote(true, Conviction::Locked1x, 10)).unwrap();
+		dispatch(&mut runtime, "charlie", vote(false, Conviction::Locked2x, 10)).unwrap();
+		run_to_block(&mut runtime, 4);
+		assert!(matches!(
+			runtime.democracy.referendum(0),
+			Some(ReferendumInfo::Finished { end: 4, approved: false })
+		));
+
+		// Only root can cancel a referendum, like the next one.
+		assert_eq!(
+			dispatch(&mut runtime, "alice", democracy::Call::cancel_referendum { referendum: 1 }),
+			Err("Bad origin: expected the root origin.")
+		);
+		let cancel = RuntimeCall::democracy(democracy::Call::cancel_referendum { referendum: 1 });
+		assert_eq!(runtime.dispatch(RawOrigin::Root, cancel), Ok(()));
+
+		run_to_block(&mut runtime, 10);
+		assert!(runtime.scheduler.lookup(&"first").is_none());
+		assert!(runtime.scheduler.lookup(&"second").is_none());
+	}
+}
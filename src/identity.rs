@@ -0,0 +1,467 @@
+use std::collections::BTreeMap;
+
+use num::CheckedAdd;
+
+use crate::{
+	support::{self, Currency, DispatchResult, ReservableCurrency},
+	system::OriginFor,
+};
+
+/// The index of a registrar.
+pub type RegistrarIndex = u32;
+
+/// The type of the deposits of the identities.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config:
+	Sized + crate::system::Config + AsRef<Pallet<Self>> + AsMut<Pallet<Self>>
+{
+	/// The currency the deposits of the identities are reserved in.
+	type Currency: ReservableCurrency<Self, Self::AccountId>;
+	/// The deposit reserved from an account for its identity.
+	const BASIC_DEPOSIT: BalanceOf<Self>;
+	/// The deposit reserved from an account for each of its sub-accounts.
+	const SUB_ACCOUNT_DEPOSIT: BalanceOf<Self>;
+	/// The maximum number of sub-accounts of an account.
+	const MAX_SUB_ACCOUNTS: u32;
+	/// The maximum number of registrars.
+	const MAX_REGISTRARS: u32;
+	/// The maximum length of each field of an identity, and of the names of sub-accounts.
+	const STRING_LIMIT: u32;
+}
+
+/// The events emitted by the Identity Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// The identity of `who` was set.
+	IdentitySet { who: T::AccountId },
+	/// The identity of `who` was cleared, and its `deposit` unreserved.
+	IdentityCleared { who: T::AccountId, deposit: BalanceOf<T> },
+	/// `registrar` was added as the registrar `index`.
+	RegistrarAdded { index: RegistrarIndex, registrar: T::AccountId },
+	/// The registrar `registrar_index` gave a `judgement` of the identity of `target`.
+	JudgementGiven { target: T::AccountId, registrar_index: RegistrarIndex, judgement: Judgement },
+	/// `sub` was added as a sub-account of `main`, reserving a `deposit` from it.
+	SubIdentityAdded { sub: T::AccountId, main: T::AccountId, deposit: BalanceOf<T> },
+	/// `sub` was removed from the sub-accounts of `main`, unreserving its `deposit`.
+	SubIdentityRemoved { sub: T::AccountId, main: T::AccountId, deposit: BalanceOf<T> },
+}
+
+/// The information an account gives about itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IdentityInfo {
+	/// The name to display next to the account.
+	pub display: Vec<u8>,
+	/// The full legal name of the owner of the account.
+	pub legal: Vec<u8>,
+	/// The website of the owner of the account.
+	pub web: Vec<u8>,
+	/// The email address of the owner of the account.
+	pub email: Vec<u8>,
+}
+
+impl IdentityInfo {
+	/// Check that all the fields fit in `limit`.
+	fn ensure_length(&self, limit: u32) -> DispatchResult {
+		let fields = [&self.display, &self.legal, &self.web, &self.email];
+		if fields.iter().any(|field| field.len() > limit as usize) {
+			return Err("Identity field is too long.");
+		}
+		Ok(())
+	}
+}
+
+/// The judgement of a registrar about the identity of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Judgement {
+	/// The information looks reasonable, but was not checked in depth.
+	Reasonable,
+	/// The information was checked in depth, and is correct.
+	KnownGood,
+	/// The information is wrong. This judgement sticks to the account even if it changes its
+	/// identity, until the registrar gives another one.
+	Erroneous,
+}
+
+/// The identity of an account, with the judgements of the registrars about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registration<T: Config> {
+	/// The information the account gave about itself.
+	pub info: IdentityInfo,
+	/// The deposit reserved for the identity.
+	pub deposit: BalanceOf<T>,
+	/// The judgements of the registrars, by registrar.
+	pub judgements: BTreeMap<RegistrarIndex, Judgement>,
+}
+
+/// This is the Identity Module.
+/// It lets accounts set an identity, reserving a deposit from them, which registrars can then
+/// judge. Accounts can also name sub-accounts, which share their identity.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The identity of each account which set one.
+	identities: BTreeMap<T::AccountId, Registration<T>>,
+	/// The main account and the name of each sub-account.
+	super_of: BTreeMap<T::AccountId, (T::AccountId, Vec<u8>)>,
+	/// The sub-accounts of each account.
+	subs_of: BTreeMap<T::AccountId, Vec<T::AccountId>>,
+	/// The registrars, by index.
+	registrars: Vec<T::AccountId>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Identity Module.
+	pub fn new() -> Self {
+		Self {
+			identities: BTreeMap::new(),
+			super_of: BTreeMap::new(),
+			subs_of: BTreeMap::new(),
+			registrars: Vec::new(),
+			events: Vec::new(),
+		}
+	}
+
+	/// Get the identity of `who`.
+	pub fn identity(&self, who: &T::AccountId) -> Option<&Registration<T>> {
+		self.identities.get(who)
+	}
+
+	/// Get the main account of `who` and its name, if it is a sub-account.
+	pub fn super_of(&self, who: &T::AccountId) -> Option<&(T::AccountId, Vec<u8>)> {
+		self.super_of.get(who)
+	}
+
+	/// Get the sub-accounts of `who`.
+	pub fn subs_of(&self, who: &T::AccountId) -> &[T::AccountId] {
+		self.subs_of.get(who).map(Vec::as_slice).unwrap_or_default()
+	}
+
+	/// Get the registrars, by index.
+	pub fn registrars(&self) -> &[T::AccountId] {
+		&self.registrars
+	}
+
+	/// Get the name to display next to `who`: its own display name, or the display name of its
+	/// main account followed by its name as a sub-account.
+	pub fn display_name(&self, who: &T::AccountId) -> Option<String> {
+		if let Some(registration) = self.identities.get(who) {
+			return Some(String::from_utf8_lossy(&registration.info.display).into_owned());
+		}
+		let (main, name) = self.super_of.get(who)?;
+		let main = self.identities.get(main)?;
+		Some(format!(
+			"{}/{}",
+			String::from_utf8_lossy(&main.info.display),
+			String::from_utf8_lossy(name)
+		))
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Set the identity of the caller, reserving `BASIC_DEPOSIT` from it if it has none yet. The
+	/// judgements of the previous identity are cleared, except the erroneous ones.
+	pub fn set_identity(
+		runtime: &mut T,
+		caller: T::AccountId,
+		info: IdentityInfo,
+	) -> DispatchResult {
+		info.ensure_length(T::STRING_LIMIT)?;
+		let identity: &mut Self = runtime.as_mut();
+		if let Some(registration) = identity.identities.get_mut(&caller) {
+			registration.info = info;
+			registration
+				.judgements
+				.retain(|_, judgement| *judgement == Judgement::Erroneous);
+		} else {
+			T::Currency::reserve(runtime, &caller, T::BASIC_DEPOSIT)?;
+			let registration =
+				Registration { info, deposit: T::BASIC_DEPOSIT, judgements: BTreeMap::new() };
+			let identity: &mut Self = runtime.as_mut();
+			identity.identities.insert(caller.clone(), registration);
+		}
+
+		let identity: &mut Self = runtime.as_mut();
+		identity.deposit_event(Event::IdentitySet { who: caller });
+		Ok(())
+	}
+
+	/// Clear the identity of the caller and remove its sub-accounts, unreserving all their
+	/// deposits.
+	pub fn clear_identity(runtime: &mut T, caller: T::AccountId) -> DispatchResult {
+		let identity: &mut Self = runtime.as_mut();
+		let registration = identity.identities.remove(&caller).ok_or("Account has no identity.")?;
+		let subs = identity.subs_of.remove(&caller).unwrap_or_default();
+		let mut deposit = registration.deposit;
+		for sub in subs {
+			identity.super_of.remove(&sub);
+			deposit = deposit.checked_add(&T::SUB_ACCOUNT_DEPOSIT).unwrap_or(deposit);
+		}
+
+		T::Currency::unreserve(runtime, &caller, deposit);
+		let identity: &mut Self = runtime.as_mut();
+		identity.deposit_event(Event::IdentityCleared { who: caller, deposit });
+		Ok(())
+	}
+
+	/// Add `account` as a registrar. Only root can add registrars.
+	pub fn add_registrar(&mut self, origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+		support::ensure_root(origin)?;
+		if self.registrars.len() >= T::MAX_REGISTRARS as usize {
+			return Err("Too many registrars.");
+		}
+		let index = self.registrars.len() as RegistrarIndex;
+		self.registrars.push(account.clone());
+		self.deposit_event(Event::RegistrarAdded { index, registrar: account });
+		Ok(())
+	}
+
+	/// Give a `judgement` of the identity of `target`, as the registrar `reg_index`, replacing
+	/// any previous judgement of that registrar.
+	pub fn provide_judgement(
+		&mut self,
+		caller: T::AccountId,
+		reg_index: RegistrarIndex,
+		target: T::AccountId,
+		judgement: Judgement,
+	) -> DispatchResult {
+		if self.registrars.get(reg_index as usize) != Some(&caller) {
+			return Err("Caller is not this registrar.");
+		}
+		let registration = self.identities.get_mut(&target).ok_or("Account has no identity.")?;
+		registration.judgements.insert(reg_index, judgement);
+		self.deposit_event(Event::JudgementGiven { target, registrar_index: reg_index, judgement });
+		Ok(())
+	}
+
+	/// Add `sub` as a sub-account of the caller, named `name`, reserving `SUB_ACCOUNT_DEPOSIT`
+	/// from the caller. The caller must have an identity.
+	pub fn add_sub(
+		runtime: &mut T,
+		caller: T::AccountId,
+		sub: T::AccountId,
+		name: Vec<u8>,
+	) -> DispatchResult {
+		if name.len() > T::STRING_LIMIT as usize {
+			return Err("Identity field is too long.");
+		}
+		let identity: &mut Self = runtime.as_mut();
+		if !identity.identities.contains_key(&caller) {
+			return Err("Account has no identity.");
+		}
+		if sub == caller || identity.super_of.contains_key(&sub) {
+			return Err("Account is already a sub-account.");
+		}
+		if identity.subs_of(&caller).len() >= T::MAX_SUB_ACCOUNTS as usize {
+			return Err("Too many sub-accounts.");
+		}
+
+		T::Currency::reserve(runtime, &caller, T::SUB_ACCOUNT_DEPOSIT)?;
+
+		let identity: &mut Self = runtime.as_mut();
+		identity.super_of.insert(sub.clone(), (caller.clone(), name));
+		identity.subs_of.entry(caller.clone()).or_default().push(sub.clone());
+		identity.deposit_event(Event::SubIdentityAdded {
+			sub,
+			main: caller,
+			deposit: T::SUB_ACCOUNT_DEPOSIT,
+		});
+		Ok(())
+	}
+
+	/// Remove `sub` from the sub-accounts of the caller, unreserving its deposit.
+	pub fn remove_sub(runtime: &mut T, caller: T::AccountId, sub: T::AccountId) -> DispatchResult {
+		let identity: &mut Self = runtime.as_mut();
+		match identity.super_of.get(&sub) {
+			Some((main, _)) if *main == caller => {},
+			_ => return Err("Account is not a sub-account of the caller."),
+		}
+		identity.super_of.remove(&sub);
+		if let Some(subs) = identity.subs_of.get_mut(&caller) {
+			subs.retain(|account| *account != sub);
+			if subs.is_empty() {
+				identity.subs_of.remove(&caller);
+			}
+		}
+
+		T::Currency::unreserve(runtime, &caller, T::SUB_ACCOUNT_DEPOSIT);
+		let identity: &mut Self = runtime.as_mut();
+		identity.deposit_event(Event::SubIdentityRemoved {
+			sub,
+			main: caller,
+			deposit: T::SUB_ACCOUNT_DEPOSIT,
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Event, IdentityInfo, Judgement};
+	use crate::{balances, identity, support::RawOrigin, testing::account};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[event]
+		identity: identity::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = String;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl identity::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		const BASIC_DEPOSIT: u32 = 10;
+		const SUB_ACCOUNT_DEPOSIT: u32 = 5;
+		const MAX_SUB_ACCOUNTS: u32 = 2;
+		const MAX_REGISTRARS: u32 = 1;
+		const STRING_LIMIT: u32 = 20;
+	}
+
+	crate::testing::impl_dispatch!(identity, origin);
+
+	fn signed(name: &str) -> RawOrigin<String> {
+		RawOrigin::Signed(account(name))
+	}
+
+	fn info(display: &str) -> IdentityInfo {
+		IdentityInfo {
+			display: display.as_bytes().to_vec(),
+			email: b"alice@example.com".to_vec(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn identity_with_judgements() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+
+		let set_identity = |display| identity::Call::set_identity { info: info(display) };
+		assert_eq!(
+			dispatch(&mut runtime, signed("bob"), set_identity("Bob")),
+			Err("Not enough funds!")
+		);
+		let mut too_long = info("Alice");
+		too_long.legal = b"Alice Margaret Example Smith".to_vec();
+		let set_too_long = identity::Call::set_identity { info: too_long };
+		assert_eq!(
+			dispatch(&mut runtime, signed("alice"), set_too_long),
+			Err("Identity field is too long.")
+		);
+		dispatch(&mut runtime, signed("alice"), set_identity("Alice")).unwrap();
+		assert_eq!(runtime.balances.balance(&account("alice")), 90);
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 10);
+		assert_eq!(runtime.identity.display_name(&account("alice")), Some("Alice".to_string()));
+
+		// Only root adds registrars, and only registrars give judgements.
+		let add_registrar = identity::Call::add_registrar { account: account("registrar") };
+		assert_eq!(
+			dispatch(&mut runtime, signed("alice"), add_registrar.clone()),
+			Err("Bad origin: expected the root origin.")
+		);
+		dispatch(&mut runtime, RawOrigin::Root, add_registrar.clone()).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, RawOrigin::Root, add_registrar),
+			Err("Too many registrars.")
+		);
+		let judge = |judgement| identity::Call::provide_judgement {
+			reg_index: 0,
+			target: account("alice"),
+			judgement,
+		};
+		assert_eq!(
+			dispatch(&mut runtime, signed("alice"), judge(Judgement::KnownGood)),
+			Err("Caller is not this registrar.")
+		);
+		dispatch(&mut runtime, signed("registrar"), judge(Judgement::KnownGood)).unwrap();
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::identity(Event::JudgementGiven {
+				judgement: Judgement::KnownGood,
+				..
+			}))
+		));
+
+		// Changing the identity clears the judgements, except the erroneous ones.
+		dispatch(&mut runtime, signed("alice"), set_identity("Alicia")).unwrap();
+		assert!(runtime.identity.identity(&account("alice")).unwrap().judgements.is_empty());
+		dispatch(&mut runtime, signed("registrar"), judge(Judgement::Erroneous)).unwrap();
+		dispatch(&mut runtime, signed("alice"), set_identity("Alice")).unwrap();
+		let registration = runtime.identity.identity(&account("alice")).unwrap();
+		assert_eq!(registration.judgements.get(&0), Some(&Judgement::Erroneous));
+		// The deposit is only reserved once.
+		assert_eq!(runtime.balances.balance(&account("alice")), 90);
+	}
+
+	#[test]
+	fn sub_accounts() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+
+		let add_sub = |sub| identity::Call::add_sub { sub: account(sub), name: b"work".to_vec() };
+		assert_eq!(
+			dispatch(&mut runtime, signed("alice"), add_sub("bob")),
+			Err("Account has no identity.")
+		);
+		let set_identity = identity::Call::set_identity { info: info("Alice") };
+		dispatch(&mut runtime, signed("alice"), set_identity).unwrap();
+		dispatch(&mut runtime, signed("alice"), add_sub("bob")).unwrap();
+		dispatch(&mut runtime, signed("alice"), add_sub("charlie")).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, signed("alice"), add_sub("bob")),
+			Err("Account is already a sub-account.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, signed("alice"), add_sub("dave")),
+			Err("Too many sub-accounts.")
+		);
+		assert_eq!(runtime.balances.balance(&account("alice")), 80);
+		assert_eq!(runtime.identity.display_name(&account("bob")), Some("Alice/work".to_string()));
+
+		let remove_sub = identity::Call::remove_sub { sub: account("bob") };
+		assert_eq!(
+			dispatch(&mut runtime, signed("charlie"), remove_sub.clone()),
+			Err("Account is not a sub-account of the caller.")
+		);
+		dispatch(&mut runtime, signed("alice"), remove_sub).unwrap();
+		assert_eq!(runtime.identity.display_name(&account("bob")), None);
+		assert_eq!(runtime.balances.balance(&account("alice")), 85);
+
+		// Clearing the identity removes the sub-accounts, and returns all the deposits.
+		dispatch(&mut runtime, signed("alice"), identity::Call::clear_identity {}).unwrap();
+		assert_eq!(runtime.balances.balance(&account("alice")), 100);
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 0);
+		assert!(runtime.identity.subs_of(&account("alice")).is_empty());
+		assert_eq!(runtime.identity.display_name(&account("charlie")), None);
+	}
+}
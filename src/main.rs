@@ -1,36 +1,268 @@
-use support::Dispatch;
-
-use crate::types::Block;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+mod assets;
 mod balances;
+mod democracy;
+mod identity;
+mod merkle;
+mod multisig;
+mod nfts;
 mod proof_of_existence;
+mod proxy;
+mod scheduler;
+mod staking;
 mod support;
 mod system;
+#[cfg(test)]
+mod testing;
+mod timestamp;
+mod treasury;
+mod utility;
+mod vesting;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[macros::runtime]
 pub struct Runtime {
 	system: system::Pallet<Self>,
+	#[inherent]
+	timestamp: timestamp::Pallet<Self>,
+	#[hooks]
+	#[event]
 	balances: balances::Pallet<Self>,
+	#[hooks]
 	proof_of_existence: proof_of_existence::Pallet<Self>,
+	#[hooks]
+	scheduler: scheduler::Pallet<Self>,
+	#[event]
+	utility: utility::Pallet<Self>,
+	#[event]
+	multisig: multisig::Pallet<Self>,
+	#[event]
+	proxy: proxy::Pallet<Self>,
+	#[hooks]
+	#[event]
+	staking: staking::Pallet<Self>,
+	#[hooks]
+	#[event]
+	democracy: democracy::Pallet<Self>,
+	#[hooks]
+	#[event]
+	treasury: treasury::Pallet<Self>,
+	#[event]
+	vesting: vesting::Pallet<Self>,
+	#[event]
+	assets: assets::Pallet<Self>,
+	#[event]
+	nfts: nfts::Pallet<Self>,
+	#[event]
+	identity: identity::Pallet<Self>,
 }
 
 impl system::Config for Runtime {
-	type BlockNumber = u32;
+	type BlockNumber = types::BlockNumber;
+
+	type AccountId = types::AccountId;
 
-	type AccountId = String;
+	type Nonce = types::Nonce;
 
-	type Nonce = u32;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl timestamp::Config for Runtime {
+	type Moment = types::Moment;
+
+	const MINIMUM_PERIOD: u64 = 3_000;
+
+	const MAXIMUM_DRIFT: u64 = 30_000;
 }
 
 impl balances::Config for Runtime {
-	type Balance = u128;
+	type Balance = types::Balance;
+
+	type DustRemoval = treasury::Pallet<Runtime>;
+
+	const EXISTENTIAL_DEPOSIT: u128 = 10;
 }
 
 impl proof_of_existence::Config for Runtime {
-    type Content = &'static str;
+	type Content = support::H256;
+
+	type Time = timestamp::Pallet<Runtime>;
+
+	type Currency = balances::Pallet<Runtime>;
+
+	const MAX_NOTE_LENGTH: u32 = 256;
+
+	const CLAIM_DEPOSIT: u128 = 10;
+
+	const NOTE_DEPOSIT_PER_BYTE: u128 = 1;
+}
+
+impl scheduler::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	type TaskName = &'static str;
+
+	const MAXIMUM_WEIGHT: support::Weight = 1_000;
+
+	const TASK_WEIGHT: support::Weight = 100;
+
+	const MAX_SCHEDULED_PER_BLOCK: u32 = 50;
+
+	const TASK_DEPOSIT: u128 = 10;
+}
+
+impl utility::Config for Runtime {}
+
+impl multisig::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	const DEPOSIT: u128 = 10;
+
+	const MAX_SIGNATORIES: u32 = 16;
 }
 
+/// The scopes of the calls a proxy can make on behalf of another account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Only transfers of balance.
+	Transfer,
+	/// Only proof of existence claims, which can be used as a hot key that never moves funds.
+	ProofOfExistence,
+	/// Any call which cannot move, reserve or lock funds.
+	NonTransfer,
+}
+
+impl support::InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, call: &RuntimeCall) -> bool {
+		use support::GetCallMetadata;
+
+		// Calls which dispatch other calls are never allowed to restricted proxies, since the
+		// calls they dispatch are not filtered.
+		let dispatches_calls =
+			matches!(call.pallet_name(), "scheduler" | "utility" | "multisig" | "proxy");
+		let moves_funds = matches!(call.pallet_name(), "balances" | "vesting" | "assets" | "nfts");
+		let claims = matches!(
+			(call.pallet_name(), call.call_name()),
+			("proof_of_existence", "create_claim" | "revoke_claim" | "renew_claim")
+		);
+		// A claim is worth as much as the content it proves, so giving it away is a transfer too.
+		let transfers_claim =
+			(call.pallet_name(), call.call_name()) == ("proof_of_existence", "transfer_claim");
+		// Calls which reserve or lock funds can make the account lose them, through slashes or
+		// forfeited deposits, and keep them out of reach in the meantime.
+		let holds_funds = matches!(
+			(call.pallet_name(), call.call_name()),
+			("proof_of_existence", "create_claim")
+				| ("staking", "bond" | "bond_extra")
+				| ("democracy", "propose" | "second" | "vote")
+				| ("treasury", "propose_spend")
+				| ("identity", "set_identity" | "add_sub")
+		);
+		match self {
+			ProxyType::Any => true,
+			ProxyType::Transfer => call.pallet_name() == "balances",
+			ProxyType::ProofOfExistence => claims,
+			ProxyType::NonTransfer => {
+				!moves_funds && !dispatches_calls && !transfers_claim && !holds_funds
+			},
+		}
+	}
+}
+
+impl proxy::Config for Runtime {
+	type ProxyType = ProxyType;
+
+	const MAX_PROXIES: u32 = 32;
+
+	const MAX_PENDING: u32 = 32;
+}
+
+impl staking::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	type Slash = treasury::Pallet<Runtime>;
+
+	type RewardRemainder = treasury::Pallet<Runtime>;
+
+	const BLOCKS_PER_ERA: u32 = 10;
+
+	const BONDING_DURATION: staking::EraIndex = 3;
+
+	const ERA_REWARD: u128 = 1_000;
+
+	const MIN_BOND: u128 = 10;
+
+	const MAX_NOMINATIONS: u32 = 16;
+}
+
+impl democracy::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	const MINIMUM_DEPOSIT: u128 = 100;
+
+	const LAUNCH_PERIOD: u32 = 20;
+
+	const VOTING_PERIOD: u32 = 20;
+
+	const ENACTMENT_PERIOD: u32 = 10;
+
+	const VOTE_LOCKING_PERIOD: u32 = 10;
+}
+
+impl treasury::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	const SPEND_PERIOD: u32 = 20;
+
+	const BURN: u8 = 1;
+
+	const PROPOSAL_BOND: u128 = 50;
+}
+
+impl vesting::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	const MIN_VESTED_TRANSFER: u128 = 10;
+
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+impl assets::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	type AssetId = u32;
+
+	type AssetBalance = u128;
+
+	const ASSET_DEPOSIT: u128 = 100;
+
+	const STRING_LIMIT: u32 = 50;
+}
+
+impl nfts::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	const STRING_LIMIT: u32 = 128;
+
+	const MAX_ATTRIBUTES: u32 = 32;
+}
+
+impl identity::Config for Runtime {
+	type Currency = balances::Pallet<Runtime>;
+
+	const BASIC_DEPOSIT: u128 = 10;
+
+	const SUB_ACCOUNT_DEPOSIT: u128 = 2;
+
+	const MAX_SUB_ACCOUNTS: u32 = 100;
+
+	const MAX_REGISTRARS: u32 = 20;
+
+	const STRING_LIMIT: u32 = 64;
+}
 
 // These are the concrete types we will use in our simple state machine.
 // Modules are configured for these types directly, and they satisfy all of our
@@ -40,78 +272,208 @@ mod types {
 	pub type Balance = u128;
 	pub type BlockNumber = u32;
 	pub type Nonce = u32;
+	pub type Moment = u64;
 	pub type Extrinsic = crate::support::Extrinsic<AccountId, crate::RuntimeCall>;
 	pub type Header = crate::support::Header<BlockNumber>;
 	pub type Block = crate::support::Block<Header, Extrinsic>;
 }
 
-
 fn main() {
 	let mut runtime = Runtime::new();
 	let alice = &"alice".to_string();
 	let bob = &"bob".to_string();
-	let charlie = &"charlie".to_string();
-	runtime.balances.set_balance(alice.into(), 100);
-
-	let block_1 = Block {
-		header: support::Header { block_number: 1 },
-		extrinsics: vec![support::Extrinsic {
-			caller: "alice".to_string(),
-			call: RuntimeCall::balances(balances::Call::transfer {
-				to: "bob".to_string(),
-				amount: 69,
-			}),
-		}],
+	runtime.balances.set_balance(alice.into(), 100).unwrap();
+
+	// The block author provides the current time to each block through an inherent. We pretend a
+	// new block is authored every six seconds.
+	let start: types::Moment = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("The clock is after the unix epoch.")
+		.as_millis()
+		.try_into()
+		.expect("The time in milliseconds fits in a `Moment`.");
+	let inherent_data = |block_number: types::BlockNumber| {
+		let mut data = support::InherentData::new();
+		data.put_data(
+			timestamp::INHERENT_IDENTIFIER,
+			start + types::Moment::from(block_number) * 6_000,
+		);
+		data
 	};
 
-    let block_2 = types::Block {
-        header: support::Header { block_number: 2 },
-        extrinsics: vec![
-            support::Extrinsic {
-                caller: alice.clone(),
-                call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                    claim: "Hello, world!",
-                }),
-            },
-            support::Extrinsic {
-                caller: bob.clone(),
-                call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                    claim: "Hello, world!",
-                }),
-            },
-        ],
-    };
-
-    let block_3 = types::Block {
-        header: support::Header { block_number: 3 },
-        extrinsics: vec![
-            support::Extrinsic {
-                caller: alice.clone(),
-                call: RuntimeCall::proof_of_existence(proof_of_existence::Call::revoke_claim {
-                    claim: "Hello, world!",
-                }),
-            },
-            support::Extrinsic {
-                caller: bob.clone(),
-                call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                    claim: "Hello, world!",
-                }),
-            },
-        ],
-    };
+	let block_1: types::Block = runtime.build_block(
+		&inherent_data(1),
+		vec![types::Extrinsic::new_signed(
+			alice.clone(),
+			RuntimeCall::balances(balances::Call::transfer { to: bob.clone(), amount: 69 }),
+		)],
+	);
 
 	runtime
 		.execute_block(block_1)
 		.expect("All blocks being executed must be valid.");
 
+	let block_2: types::Block = runtime.build_block(
+		&inherent_data(2),
+		vec![
+			types::Extrinsic::new_signed(
+				alice.clone(),
+				RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+					claim: proof_of_existence::hash_content(b"Hello, world!"),
+					note: None,
+					lifetime: None,
+				}),
+			),
+			types::Extrinsic::new_signed(
+				bob.clone(),
+				RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+					claim: proof_of_existence::hash_content(b"Hello, world!"),
+					note: None,
+					lifetime: None,
+				}),
+			),
+		],
+	);
+
 	runtime
 		.execute_block(block_2)
 		.expect("All blocks being executed must be valid.");
 
+	let block_3: types::Block = runtime.build_block(
+		&inherent_data(3),
+		vec![
+			types::Extrinsic::new_signed(
+				alice.clone(),
+				RuntimeCall::proof_of_existence(proof_of_existence::Call::revoke_claim {
+					claim: proof_of_existence::hash_content(b"Hello, world!"),
+				}),
+			),
+			types::Extrinsic::new_signed(
+				bob.clone(),
+				RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+					claim: proof_of_existence::hash_content(b"Hello, world!"),
+					note: None,
+					lifetime: None,
+				}),
+			),
+		],
+	);
+
 	runtime
 		.execute_block(block_3)
 		.expect("All blocks being executed must be valid.");
 
+	// A batch of documents is notarized with a single claim on the root of their Merkle tree,
+	// built off chain. Here the batch holds this very program.
+	let documents = [
+		proof_of_existence::hash_content(b"Hello, world!"),
+		std::env::current_exe()
+			.and_then(proof_of_existence::hash_file)
+			.expect("The running program can be read."),
+		proof_of_existence::hash_content(b"Goodbye, world!"),
+	];
+	let batch = merkle::MerkleTree::new(&documents).expect("The batch is not empty.");
+	let block_4: types::Block = runtime.build_block(
+		&inherent_data(4),
+		vec![types::Extrinsic::new_signed(
+			alice.clone(),
+			RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+				claim: batch.root(),
+				note: Some("Batch".to_string()),
+				lifetime: None,
+			}),
+		)],
+	);
+
+	runtime
+		.execute_block(block_4)
+		.expect("All blocks being executed must be valid.");
+
+	// Each document of the batch is then proven to be notarized with its own Merkle proof.
+	let proof = batch.proof(1).expect("The batch holds the program.");
+	runtime
+		.proof_of_existence
+		.verify_batch(&batch.root(), &documents[1], &proof, 4)
+		.expect("The program was notarized in the batch.");
+	runtime
+		.balances
+		.check_issuance()
+		.expect("The total issuance must match the balances of all accounts.");
+
 	// inspect the runtime state
 	println!("{:#?}", runtime);
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		balances, democracy, identity, proof_of_existence, staking, support::InstanceFilter,
+		treasury, ProxyType, RuntimeCall,
+	};
+
+	fn claim_call(call: &str) -> RuntimeCall {
+		let claim = proof_of_existence::hash_content(b"Hello, world!");
+		RuntimeCall::proof_of_existence(match call {
+			"create_claim" => {
+				proof_of_existence::Call::create_claim { claim, note: None, lifetime: None }
+			},
+			"revoke_claim" => proof_of_existence::Call::revoke_claim { claim },
+			"renew_claim" => proof_of_existence::Call::renew_claim { claim, lifetime: 10 },
+			_ => proof_of_existence::Call::transfer_claim { claim, new_owner: "bob".to_string() },
+		})
+	}
+
+	#[test]
+	fn proof_of_existence_proxies_never_move_funds_or_claims() {
+		for call in ["create_claim", "revoke_claim", "renew_claim"] {
+			assert!(ProxyType::ProofOfExistence.filter(&claim_call(call)));
+		}
+		assert!(!ProxyType::ProofOfExistence.filter(&claim_call("transfer_claim")));
+		let transfer = balances::Call::transfer { to: "bob".to_string(), amount: 10 };
+		assert!(!ProxyType::ProofOfExistence.filter(&RuntimeCall::balances(transfer)));
+	}
+
+	#[test]
+	fn non_transfer_proxies_never_move_funds_or_claims() {
+		for call in ["revoke_claim", "renew_claim"] {
+			assert!(ProxyType::NonTransfer.filter(&claim_call(call)));
+		}
+		assert!(!ProxyType::NonTransfer.filter(&claim_call("transfer_claim")));
+		let transfer = balances::Call::transfer { to: "bob".to_string(), amount: 10 };
+		assert!(!ProxyType::NonTransfer.filter(&RuntimeCall::balances(transfer)));
+		let clear = identity::Call::clear_identity {};
+		assert!(ProxyType::NonTransfer.filter(&RuntimeCall::identity(clear)));
+		assert!(ProxyType::Any.filter(&claim_call("transfer_claim")));
+	}
+
+	#[test]
+	fn non_transfer_proxies_never_reserve_or_lock_funds() {
+		let bob = || "bob".to_string();
+		let transfer = balances::Call::transfer { to: bob(), amount: 10 };
+		let vote = democracy::Vote { aye: true, conviction: democracy::Conviction::Locked1x };
+		let calls = [
+			claim_call("create_claim"),
+			RuntimeCall::staking(staking::Call::bond { value: 10 }),
+			RuntimeCall::staking(staking::Call::bond_extra { value: 10 }),
+			RuntimeCall::democracy(democracy::Call::propose {
+				proposal: Box::new(RuntimeCall::balances(transfer)),
+				value: 10,
+			}),
+			RuntimeCall::democracy(democracy::Call::second { proposal: 0 }),
+			RuntimeCall::democracy(democracy::Call::vote { referendum: 0, vote, balance: 10 }),
+			RuntimeCall::treasury(treasury::Call::propose_spend { value: 10, beneficiary: bob() }),
+			RuntimeCall::identity(identity::Call::set_identity { info: Default::default() }),
+			RuntimeCall::identity(identity::Call::add_sub { sub: bob(), name: Vec::new() }),
+		];
+		for call in calls {
+			assert!(!ProxyType::NonTransfer.filter(&call), "{call:?}");
+			assert!(ProxyType::Any.filter(&call));
+		}
+
+		// Calls which release funds are still allowed.
+		let unbond = RuntimeCall::staking(staking::Call::unbond { value: 10 });
+		assert!(ProxyType::NonTransfer.filter(&unbond));
+		let unlock = RuntimeCall::democracy(democracy::Call::unlock {});
+		assert!(ProxyType::NonTransfer.filter(&unlock));
+	}
+}
@@ -0,0 +1,159 @@
+use crate::support::{self, H256};
+
+/// The prefix of the hash of a leaf, so that a leaf can never be mistaken for an inner node.
+const LEAF_PREFIX: u8 = 0;
+/// The prefix of the hash of an inner node.
+const NODE_PREFIX: u8 = 1;
+
+/// Hash a `leaf` of the tree, like the hash of a document.
+fn hash_leaf(leaf: &H256) -> H256 {
+	let mut data = [0u8; 33];
+	data[0] = LEAF_PREFIX;
+	data[1..].copy_from_slice(leaf);
+	support::sha2_256(&data)
+}
+
+/// Hash the `left` and `right` children of an inner node.
+fn hash_node(left: &H256, right: &H256) -> H256 {
+	let mut data = [0u8; 65];
+	data[0] = NODE_PREFIX;
+	data[1..33].copy_from_slice(left);
+	data[33..].copy_from_slice(right);
+	support::sha2_256(&data)
+}
+
+/// A Merkle tree over some leaves, like the hashes of a batch of documents, which is built off
+/// chain. Only its root needs to be claimed, and each document is later proven to be part of the
+/// batch with its `MerkleProof`.
+///
+/// The last node of a layer with an odd number of nodes is moved up to the next layer as is,
+/// rather than paired with a copy of itself, so that no two batches share a root.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+	/// The hashes of each layer of the tree, from the leaves to the root.
+	layers: Vec<Vec<H256>>,
+}
+
+/// A proof that some leaf is part of the Merkle tree with a given root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+	/// The position of the leaf in the tree.
+	pub leaf_index: usize,
+	/// The number of leaves of the tree.
+	pub leaf_count: usize,
+	/// The siblings of the nodes on the path from the leaf to the root, from the bottom up.
+	pub siblings: Vec<H256>,
+}
+
+impl MerkleTree {
+	/// Build the tree over `leaves`, which must not be empty.
+	pub fn new(leaves: &[H256]) -> Result<Self, &'static str> {
+		if leaves.is_empty() {
+			return Err("A Merkle tree needs at least one leaf.");
+		}
+		let mut layers = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+		while let Some(layer) = layers.last().filter(|layer| layer.len() > 1) {
+			let next = layer
+				.chunks(2)
+				.map(|pair| match pair {
+					[left, right] => hash_node(left, right),
+					[last] => *last,
+					_ => unreachable!("Chunks hold one or two nodes."),
+				})
+				.collect();
+			layers.push(next);
+		}
+		Ok(Self { layers })
+	}
+
+	/// The root of the tree, which is claimed for the whole batch.
+	pub fn root(&self) -> H256 {
+		self.layers.last().expect("A tree has at least one layer.")[0]
+	}
+
+	/// The proof that the leaf at `index` is part of the tree, if there is such a leaf.
+	pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+		let leaf_count = self.layers[0].len();
+		if index >= leaf_count {
+			return None;
+		}
+		let mut siblings = Vec::new();
+		let mut position = index;
+		for layer in &self.layers[..self.layers.len() - 1] {
+			if let Some(sibling) = layer.get(position ^ 1) {
+				siblings.push(*sibling);
+			}
+			position /= 2;
+		}
+		Some(MerkleProof { leaf_index: index, leaf_count, siblings })
+	}
+}
+
+/// Check that `leaf` is part of the Merkle tree with `root`, using its `proof`.
+pub fn verify(root: &H256, leaf: &H256, proof: &MerkleProof) -> bool {
+	if proof.leaf_index >= proof.leaf_count {
+		return false;
+	}
+	let mut siblings = proof.siblings.iter();
+	let mut hash = hash_leaf(leaf);
+	let (mut position, mut width) = (proof.leaf_index, proof.leaf_count);
+	while width > 1 {
+		// The last node of a layer with an odd width has no sibling, and moves up as is.
+		if position % 2 == 1 || position + 1 < width {
+			let Some(sibling) = siblings.next() else { return false };
+			hash = if position % 2 == 1 {
+				hash_node(sibling, &hash)
+			} else {
+				hash_node(&hash, sibling)
+			};
+		}
+		position /= 2;
+		width = width.div_ceil(2);
+	}
+	siblings.next().is_none() && hash == *root
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{verify, MerkleTree};
+	use crate::support::sha2_256;
+
+	#[test]
+	fn proofs_verify_against_the_root() {
+		assert_eq!(MerkleTree::new(&[]).err(), Some("A Merkle tree needs at least one leaf."));
+
+		for count in 1..=9u8 {
+			let leaves = (0..count).map(|i| sha2_256(&[i])).collect::<Vec<_>>();
+			let tree = MerkleTree::new(&leaves).unwrap();
+			for (index, leaf) in leaves.iter().enumerate() {
+				let proof = tree.proof(index).unwrap();
+				assert!(verify(&tree.root(), leaf, &proof));
+				// The proof is only valid for its own leaf.
+				assert!(!verify(&tree.root(), &sha2_256(b"other"), &proof));
+			}
+			assert_eq!(tree.proof(count.into()), None);
+		}
+	}
+
+	#[test]
+	fn tampered_proofs_are_rejected() {
+		let leaves = (0..5u8).map(|i| sha2_256(&[i])).collect::<Vec<_>>();
+		let tree = MerkleTree::new(&leaves).unwrap();
+		let proof = tree.proof(2).unwrap();
+
+		let mut moved = proof.clone();
+		moved.leaf_index = 3;
+		assert!(!verify(&tree.root(), &leaves[2], &moved));
+		let mut truncated = proof.clone();
+		truncated.siblings.pop();
+		assert!(!verify(&tree.root(), &leaves[2], &truncated));
+		let mut extended = proof;
+		extended.siblings.push(leaves[0]);
+		assert!(!verify(&tree.root(), &leaves[2], &extended));
+
+		// A batch of the same leaves, with the last one repeated, has another root.
+		let mut repeated = leaves.clone();
+		repeated.push(leaves[4]);
+		assert_ne!(MerkleTree::new(&repeated).unwrap().root(), tree.root());
+	}
+}
@@ -0,0 +1,587 @@
+use core::hash::Hash;
+use std::collections::BTreeMap;
+
+use crate::{
+	support::{
+		self, CallOf, Currency, DeriveAccountId, Dispatch, DispatchResult, RawOrigin,
+		ReservableCurrency, H256,
+	},
+	system::OriginFor,
+};
+
+/// The seed of the multisig accounts derived by the Multisig Module.
+pub const PALLET_ID: &[u8; 8] = b"py/mltsg";
+
+/// The type of the deposits of the multisig operations.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config:
+	Sized
+	+ crate::system::Config<AccountId: Hash + DeriveAccountId>
+	+ Dispatch<Caller = OriginFor<Self>, Call: Hash>
+	+ AsRef<crate::system::Pallet<Self>>
+	+ AsMut<Pallet<Self>>
+{
+	/// The currency the deposits of the multisig operations are reserved in.
+	type Currency: ReservableCurrency<Self, Self::AccountId>;
+	/// The deposit reserved from the account which opens a multisig operation, until the operation is
+	/// executed or cancelled.
+	const DEPOSIT: BalanceOf<Self>;
+	/// The maximum number of signatories of a multisig account, including the caller.
+	const MAX_SIGNATORIES: u32;
+}
+
+/// The events emitted by the Multisig Module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<AccountId> {
+	/// `approving` opened a new operation of `multisig`, for the call with `call_hash`.
+	NewMultisig { approving: AccountId, multisig: AccountId, call_hash: H256 },
+	/// `approving` approved the operation of `multisig` for the call with `call_hash`.
+	MultisigApproval { approving: AccountId, multisig: AccountId, call_hash: H256 },
+	/// `approving` executed the operation of `multisig` for the call with `call_hash`, which
+	/// returned `result`.
+	MultisigExecuted {
+		approving: AccountId,
+		multisig: AccountId,
+		call_hash: H256,
+		result: DispatchResult,
+	},
+	/// `cancelling` cancelled the operation of `multisig` for the call with `call_hash`.
+	MultisigCancelled { cancelling: AccountId, multisig: AccountId, call_hash: H256 },
+}
+
+/// A point in the history of the chain: the block, and the index of an extrinsic within it.
+///
+/// The timepoint at which an operation was opened must be given to approve it, so that an approval
+/// meant for an operation cannot count towards a later one for the same call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timepoint<BlockNumber> {
+	/// The block the extrinsic is in.
+	pub height: BlockNumber,
+	/// The index of the extrinsic in the block.
+	pub index: u32,
+}
+
+/// An operation of a multisig account which is waiting for enough approvals.
+#[derive(Debug, Clone)]
+pub struct Multisig<T: Config> {
+	/// The timepoint of the extrinsic which opened the operation.
+	pub when: Timepoint<T::BlockNumber>,
+	/// The account which opened the operation, and paid its deposit.
+	pub depositor: T::AccountId,
+	/// The deposit reserved for the operation.
+	pub deposit: BalanceOf<T>,
+	/// The signatories which approved the operation so far.
+	pub approvals: Vec<T::AccountId>,
+}
+
+/// This is the Multisig Module.
+/// It allows a set of signatories to jointly control an account, which dispatches a call once
+/// enough of them have approved it.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The open operations of each multisig account, by the hash of their call.
+	multisigs: BTreeMap<(T::AccountId, H256), Multisig<T>>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T::AccountId>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Multisig Module.
+	pub fn new() -> Self {
+		Self { multisigs: BTreeMap::new(), events: Vec::new() }
+	}
+
+	/// Get the open operation of the `multisig` account for the call with `call_hash`.
+	pub fn multisig(&self, multisig: &T::AccountId, call_hash: &H256) -> Option<&Multisig<T>> {
+		self.multisigs.get(&(multisig.clone(), *call_hash))
+	}
+
+	/// The account of the multisig with the given `signatories`, which must be sorted, and
+	/// `threshold`.
+	pub fn multi_account_id(signatories: &[T::AccountId], threshold: u16) -> T::AccountId {
+		T::AccountId::derive_account_id(&support::hash_of(&(PALLET_ID, signatories, threshold)))
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T::AccountId>) {
+		self.events.push(event);
+	}
+
+	/// The timepoint of the extrinsic being executed.
+	fn timepoint(runtime: &T) -> Timepoint<T::BlockNumber> {
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		Timepoint {
+			height: system.block_number(),
+			index: system.extrinsic_index().unwrap_or_default(),
+		}
+	}
+
+	/// Check the `other_signatories` of a multisig are sorted and unique, and insert `who` among
+	/// them, returning all the signatories of the multisig.
+	fn ensure_signatories(
+		who: T::AccountId,
+		threshold: u16,
+		mut other_signatories: Vec<T::AccountId>,
+	) -> Result<Vec<T::AccountId>, &'static str> {
+		if threshold == 0 {
+			return Err("The threshold must be at least one.");
+		}
+		if !other_signatories.windows(2).all(|pair| pair[0] < pair[1]) {
+			return Err("The other signatories must be sorted and unique.");
+		}
+		let index = other_signatories
+			.binary_search(&who)
+			.err()
+			.ok_or("The caller must not be one of the other signatories.")?;
+		other_signatories.insert(index, who);
+
+		if other_signatories.len() > T::MAX_SIGNATORIES as usize {
+			return Err("Too many signatories.");
+		}
+		if usize::from(threshold) > other_signatories.len() {
+			return Err("The threshold is greater than the number of signatories.");
+		}
+		Ok(other_signatories)
+	}
+
+	/// Approve the call with `call_hash` on behalf of `who`, one of the signatories of the multisig
+	/// with the `other_signatories` and `threshold`. The `maybe_timepoint` of the operation must be
+	/// given if it is already open, and only then.
+	///
+	/// If `maybe_call` is given and the operation reaches its threshold with this approval, or had
+	/// already reached it, the call is dispatched on behalf of the multisig account, and the
+	/// deposit of the operation is returned. Otherwise the approval is recorded, opening a new
+	/// operation if needed.
+	fn operate(
+		runtime: &mut T,
+		who: T::AccountId,
+		threshold: u16,
+		other_signatories: Vec<T::AccountId>,
+		maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
+		call_hash: H256,
+		maybe_call: Option<CallOf<T>>,
+	) -> DispatchResult {
+		let signatories = Self::ensure_signatories(who.clone(), threshold, other_signatories)?;
+		let id = Self::multi_account_id(&signatories, threshold);
+		let key = (id.clone(), call_hash);
+		let when = Self::timepoint(runtime);
+
+		let multisig: &mut Self = runtime.as_mut();
+		match (multisig.multisigs.get(&key), maybe_timepoint) {
+			(Some(operation), Some(timepoint)) if operation.when != timepoint => {
+				return Err("The timepoint does not match the multisig operation.");
+			},
+			(Some(_), None) => return Err("The timepoint of the multisig operation is missing."),
+			(None, Some(_)) => return Err("A new multisig operation must not have a timepoint."),
+			_ => {},
+		}
+		let approvals = match multisig.multisigs.get(&key) {
+			Some(operation) if operation.approvals.contains(&who) => operation.approvals.len(),
+			Some(operation) => operation.approvals.len() + 1,
+			None => 1,
+		};
+
+		match maybe_call {
+			Some(call) if approvals >= usize::from(threshold) => {
+				if let Some(operation) = multisig.multisigs.remove(&key) {
+					T::Currency::unreserve(runtime, &operation.depositor, operation.deposit);
+				}
+
+				let result = runtime.dispatch(RawOrigin::Signed(id.clone()), call);
+				let multisig: &mut Self = runtime.as_mut();
+				multisig.deposit_event(Event::MultisigExecuted {
+					approving: who,
+					multisig: id,
+					call_hash,
+					result,
+				});
+			},
+			_ => match multisig.multisigs.get_mut(&key) {
+				Some(operation) => {
+					if operation.approvals.contains(&who) {
+						return Err("Already approved.");
+					}
+					operation.approvals.push(who.clone());
+					multisig.deposit_event(Event::MultisigApproval {
+						approving: who,
+						multisig: id,
+						call_hash,
+					});
+				},
+				None => {
+					T::Currency::reserve(runtime, &who, T::DEPOSIT)?;
+
+					let multisig: &mut Self = runtime.as_mut();
+					let operation = Multisig {
+						when,
+						depositor: who.clone(),
+						deposit: T::DEPOSIT,
+						approvals: vec![who.clone()],
+					};
+					multisig.multisigs.insert(key, operation);
+					multisig.deposit_event(Event::NewMultisig {
+						approving: who,
+						multisig: id,
+						call_hash,
+					});
+				},
+			},
+		}
+		Ok(())
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Approve `call` on behalf of the multisig with the `other_signatories` and `threshold`, which
+	/// the caller is a signatory of. The `other_signatories` must be sorted.
+	///
+	/// The first approval opens a new operation, holding a deposit from the caller, and must not
+	/// give a `maybe_timepoint`. Later approvals must give the timepoint of the extrinsic which
+	/// opened the operation. The approval which reaches the `threshold` dispatches `call` on
+	/// behalf of the multisig account, and returns the deposit.
+	pub fn as_multi(
+		runtime: &mut T,
+		caller: T::AccountId,
+		threshold: u16,
+		other_signatories: Vec<T::AccountId>,
+		maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
+		call: Box<CallOf<T>>,
+	) -> DispatchResult {
+		let call_hash = support::hash_of(&call);
+		Self::operate(
+			runtime,
+			caller,
+			threshold,
+			other_signatories,
+			maybe_timepoint,
+			call_hash,
+			Some(*call),
+		)
+	}
+
+	/// Approve the call with `call_hash` on behalf of the multisig with the `other_signatories` and
+	/// `threshold`, without dispatching it. The call is only dispatched by `as_multi`. Like for
+	/// `as_multi`, the `maybe_timepoint` of the operation must be given if it is already open.
+	pub fn approve_as_multi(
+		runtime: &mut T,
+		caller: T::AccountId,
+		threshold: u16,
+		other_signatories: Vec<T::AccountId>,
+		maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
+		call_hash: H256,
+	) -> DispatchResult {
+		Self::operate(
+			runtime,
+			caller,
+			threshold,
+			other_signatories,
+			maybe_timepoint,
+			call_hash,
+			None,
+		)
+	}
+
+	/// Cancel the operation opened at `timepoint` for the call with `call_hash` of the multisig
+	/// with the `other_signatories` and `threshold`. Only the caller which opened the operation
+	/// can cancel it, which returns their deposit.
+	pub fn cancel_as_multi(
+		runtime: &mut T,
+		caller: T::AccountId,
+		threshold: u16,
+		other_signatories: Vec<T::AccountId>,
+		timepoint: Timepoint<T::BlockNumber>,
+		call_hash: H256,
+	) -> DispatchResult {
+		let signatories = Self::ensure_signatories(caller.clone(), threshold, other_signatories)?;
+		let id = Self::multi_account_id(&signatories, threshold);
+		let key = (id.clone(), call_hash);
+
+		let multisig: &mut Self = runtime.as_mut();
+		let operation = multisig.multisigs.get(&key).ok_or("Multisig operation does not exist.")?;
+		if operation.when != timepoint {
+			return Err("The timepoint does not match the multisig operation.");
+		}
+		if operation.depositor != caller {
+			return Err("Only the depositor can cancel a multisig operation.");
+		}
+		let operation = multisig.multisigs.remove(&key).expect("The operation exists.");
+		multisig.deposit_event(Event::MultisigCancelled {
+			cancelling: caller,
+			multisig: id,
+			call_hash,
+		});
+
+		T::Currency::unreserve(runtime, &operation.depositor, operation.deposit);
+		Ok(())
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T::AccountId>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Event, Timepoint};
+	use crate::{
+		balances, multisig,
+		support::{self, Extrinsic, InherentData},
+		testing::account,
+	};
+
+	#[derive(Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[event]
+		multisig: multisig::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = String;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl multisig::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		const DEPOSIT: u32 = 10;
+		const MAX_SIGNATORIES: u32 = 3;
+	}
+
+	type Multisig = multisig::Pallet<Runtime>;
+
+	fn others(names: &[&str]) -> Vec<String> {
+		names.iter().map(|name| account(name)).collect()
+	}
+
+	fn transfer(to: &str, amount: u32) -> RuntimeCall {
+		RuntimeCall::balances(balances::Call::transfer { to: account(to), amount })
+	}
+
+	fn execute_call(runtime: &mut Runtime, caller: &str, call: multisig::Call<Runtime>) {
+		let extrinsic = Extrinsic::new_signed(account(caller), RuntimeCall::multisig(call));
+		let block = runtime.build_block(&InherentData::new(), vec![extrinsic]);
+		runtime.execute_block(block).unwrap();
+	}
+
+	fn multisig_events(runtime: &Runtime) -> Vec<Event<String>> {
+		runtime
+			.system
+			.events()
+			.iter()
+			.map(|event| match event {
+				RuntimeEvent::multisig(event) => event.clone(),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn multisig_account() {
+		let signatories = others(&["alice", "bob", "charlie"]);
+		let id = Multisig::multi_account_id(&signatories, 2);
+		assert_eq!(id, Multisig::multi_account_id(&signatories, 2));
+		assert_ne!(id, Multisig::multi_account_id(&signatories, 3));
+		assert_ne!(id, Multisig::multi_account_id(&signatories[..2], 2));
+
+		// The other signatories must be sorted, and must not include the caller.
+		let result = Multisig::ensure_signatories(account("alice"), 2, others(&["charlie", "bob"]));
+		assert_eq!(result, Err("The other signatories must be sorted and unique."));
+		let result = Multisig::ensure_signatories(account("alice"), 2, others(&["alice", "bob"]));
+		assert_eq!(result, Err("The caller must not be one of the other signatories."));
+		let result = Multisig::ensure_signatories(account("alice"), 3, others(&["bob"]));
+		assert_eq!(result, Err("The threshold is greater than the number of signatories."));
+		let result = Multisig::ensure_signatories(account("alice"), 2, others(&["bob", "c", "d"]));
+		assert_eq!(result, Err("Too many signatories."));
+		let result = Multisig::ensure_signatories(account("bob"), 2, others(&["alice", "charlie"]));
+		assert_eq!(result, Ok(signatories));
+	}
+
+	#[test]
+	fn multisig_execution() {
+		let mut runtime = Runtime::new();
+		let id = Multisig::multi_account_id(&others(&["alice", "bob", "charlie"]), 2);
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+		runtime.balances.set_balance(id.clone(), 50).unwrap();
+
+		let call = transfer("dave", 30);
+		let call_hash = support::hash_of(&call);
+
+		// The first approval opens the operation, holding a deposit from alice.
+		let as_multi = |other_signatories, maybe_timepoint| multisig::Call::as_multi {
+			threshold: 2,
+			other_signatories,
+			maybe_timepoint,
+			call: Box::new(call.clone()),
+		};
+		execute_call(&mut runtime, "alice", as_multi(others(&["bob", "charlie"]), None));
+		assert_eq!(
+			multisig_events(&runtime),
+			vec![Event::NewMultisig {
+				approving: account("alice"),
+				multisig: id.clone(),
+				call_hash
+			}]
+		);
+		assert_eq!(runtime.balances.balance(&account("alice")), 90);
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 10);
+		assert_eq!(runtime.balances.balance(&account("dave")), 0);
+		let operation = runtime.multisig.multisig(&id, &call_hash).unwrap();
+		assert_eq!(operation.approvals, others(&["alice"]));
+		// The operation is known by the extrinsic which opened it.
+		let timepoint = Timepoint { height: 1, index: 0 };
+		assert_eq!(operation.when, timepoint);
+
+		// Later approvals must give the timepoint of the operation.
+		let mut dispatch = |caller: &str, call| {
+			let origin = support::RawOrigin::Signed(account(caller));
+			support::Dispatch::dispatch(&mut runtime, origin, RuntimeCall::multisig(call))
+		};
+		assert_eq!(
+			dispatch("charlie", as_multi(others(&["alice", "bob"]), None)),
+			Err("The timepoint of the multisig operation is missing.")
+		);
+		let wrong = Timepoint { height: 1, index: 1 };
+		assert_eq!(
+			dispatch("charlie", as_multi(others(&["alice", "bob"]), Some(wrong))),
+			Err("The timepoint does not match the multisig operation.")
+		);
+
+		// The second approval reaches the threshold, and dispatches the call from the multisig.
+		execute_call(&mut runtime, "charlie", as_multi(others(&["alice", "bob"]), Some(timepoint)));
+		assert_eq!(
+			multisig_events(&runtime),
+			vec![Event::MultisigExecuted {
+				approving: account("charlie"),
+				multisig: id.clone(),
+				call_hash,
+				result: Ok(()),
+			}]
+		);
+		assert_eq!(runtime.balances.balance(&account("dave")), 30);
+		assert_eq!(runtime.balances.balance(&id), 20);
+		assert_eq!(runtime.balances.balance(&account("alice")), 100);
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 0);
+		assert!(runtime.multisig.multisig(&id, &call_hash).is_none());
+
+		// A timepoint is never given for a new operation, so old approvals cannot be replayed.
+		let origin = support::RawOrigin::Signed(account("alice"));
+		let call = RuntimeCall::multisig(as_multi(others(&["bob", "charlie"]), Some(timepoint)));
+		assert_eq!(
+			support::Dispatch::dispatch(&mut runtime, origin, call),
+			Err("A new multisig operation must not have a timepoint.")
+		);
+	}
+
+	#[test]
+	fn multisig_approvals() {
+		let mut runtime = Runtime::new();
+		let id = Multisig::multi_account_id(&others(&["alice", "bob", "charlie"]), 3);
+		runtime.balances.set_balance(account("bob"), 100).unwrap();
+		runtime.balances.set_balance(id.clone(), 50).unwrap();
+
+		let call = transfer("dave", 30);
+		let call_hash = support::hash_of(&call);
+		let approve = |other_signatories, maybe_timepoint| multisig::Call::approve_as_multi {
+			threshold: 3,
+			other_signatories,
+			maybe_timepoint,
+			call_hash,
+		};
+
+		execute_call(&mut runtime, "bob", approve(others(&["alice", "charlie"]), None));
+		let timepoint = Some(runtime.multisig.multisig(&id, &call_hash).unwrap().when);
+		// The same signatory cannot approve twice.
+		let call = RuntimeCall::multisig(approve(others(&["alice", "charlie"]), timepoint));
+		let result = support::Dispatch::dispatch(
+			&mut runtime,
+			support::RawOrigin::Signed(account("bob")),
+			call,
+		);
+		assert_eq!(result, Err("Already approved."));
+
+		// Approving only by hash never dispatches the call, even past the threshold.
+		execute_call(&mut runtime, "alice", approve(others(&["bob", "charlie"]), timepoint));
+		execute_call(&mut runtime, "charlie", approve(others(&["alice", "bob"]), timepoint));
+		assert_eq!(runtime.multisig.multisig(&id, &call_hash).unwrap().approvals.len(), 3);
+		assert_eq!(runtime.balances.balance(&account("dave")), 0);
+
+		// Any signatory can then dispatch it, even one which already approved.
+		let as_multi = multisig::Call::as_multi {
+			threshold: 3,
+			other_signatories: others(&["alice", "charlie"]),
+			maybe_timepoint: timepoint,
+			call: Box::new(transfer("dave", 30)),
+		};
+		execute_call(&mut runtime, "bob", as_multi);
+		assert_eq!(runtime.balances.balance(&account("dave")), 30);
+		assert_eq!(runtime.balances.balance(&account("bob")), 100);
+	}
+
+	#[test]
+	fn cancel_multisig() {
+		let mut runtime = Runtime::new();
+		let id = Multisig::multi_account_id(&others(&["alice", "bob"]), 2);
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+
+		let call_hash = support::hash_of(&transfer("dave", 30));
+		let approve = multisig::Call::approve_as_multi {
+			threshold: 2,
+			other_signatories: others(&["bob"]),
+			maybe_timepoint: None,
+			call_hash,
+		};
+		execute_call(&mut runtime, "alice", approve);
+		assert_eq!(runtime.balances.balance(&account("alice")), 90);
+		let timepoint = runtime.multisig.multisig(&id, &call_hash).unwrap().when;
+
+		// Only the depositor can cancel the operation, at its timepoint.
+		let cancel = |other_signatories, timepoint| {
+			RuntimeCall::multisig(multisig::Call::cancel_as_multi {
+				threshold: 2,
+				other_signatories,
+				timepoint,
+				call_hash,
+			})
+		};
+		let origin = support::RawOrigin::Signed(account("bob"));
+		let call = cancel(others(&["alice"]), timepoint);
+		let result = support::Dispatch::dispatch(&mut runtime, origin, call);
+		assert_eq!(result, Err("Only the depositor can cancel a multisig operation."));
+
+		let origin = support::RawOrigin::Signed(account("alice"));
+		let call = cancel(others(&["bob"]), Timepoint { height: 2, index: 0 });
+		let result = support::Dispatch::dispatch(&mut runtime, origin, call);
+		assert_eq!(result, Err("The timepoint does not match the multisig operation."));
+
+		let origin = support::RawOrigin::Signed(account("alice"));
+		let result =
+			support::Dispatch::dispatch(&mut runtime, origin, cancel(others(&["bob"]), timepoint));
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			multisig_events(&runtime).last(),
+			Some(&Event::MultisigCancelled {
+				cancelling: account("alice"),
+				multisig: id.clone(),
+				call_hash
+			})
+		);
+		assert_eq!(runtime.balances.balance(&account("alice")), 100);
+		assert!(runtime.multisig.multisig(&id, &call_hash).is_none());
+	}
+}
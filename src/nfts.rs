@@ -0,0 +1,702 @@
+use std::collections::BTreeMap;
+
+use num::{CheckedSub, Zero};
+
+use crate::support::{self, Currency, DispatchResult};
+
+/// The identifier of a collection.
+pub type CollectionId = u32;
+/// The identifier of an item within its collection.
+pub type ItemId = u32;
+/// The type of the prices of items.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config:
+	Sized + Clone + crate::system::Config + AsRef<Pallet<Self>> + AsMut<Pallet<Self>>
+{
+	/// The currency items are bought with.
+	type Currency: Currency<Self, Self::AccountId, Balance: Into<u128> + TryFrom<u128>>;
+	/// The maximum length of metadata, and of the keys and values of attributes.
+	const STRING_LIMIT: u32;
+	/// The maximum number of attributes of an item.
+	const MAX_ATTRIBUTES: u32;
+}
+
+/// The events emitted by the NFTs Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// The collection `collection` was created by `owner`.
+	Created { collection: CollectionId, owner: T::AccountId },
+	/// The metadata of the collection `collection` was set.
+	CollectionMetadataSet { collection: CollectionId, data: Vec<u8> },
+	/// The owner of the collection `collection` takes `royalty` percent of the sales of its items.
+	RoyaltySet { collection: CollectionId, royalty: u8 },
+	/// The items of the collection `collection` can be transferred or not.
+	TransferabilitySet { collection: CollectionId, transferable: bool },
+	/// The item `item` of the collection `collection` was minted to `owner`.
+	Issued { collection: CollectionId, item: ItemId, owner: T::AccountId },
+	/// The metadata of the item `item` of the collection `collection` was set.
+	MetadataSet { collection: CollectionId, item: ItemId, data: Vec<u8> },
+	/// The attribute `key` of the item `item` of the collection `collection` was set to `value`.
+	AttributeSet { collection: CollectionId, item: ItemId, key: Vec<u8>, value: Vec<u8> },
+	/// The attribute `key` of the item `item` of the collection `collection` was cleared.
+	AttributeCleared { collection: CollectionId, item: ItemId, key: Vec<u8> },
+	/// The item `item` of the collection `collection` was transferred from `from` to `to`.
+	Transferred { collection: CollectionId, item: ItemId, from: T::AccountId, to: T::AccountId },
+	/// The item `item` of the collection `collection` was burned by `owner`.
+	Burned { collection: CollectionId, item: ItemId, owner: T::AccountId },
+	/// `delegate` can transfer the item `item` of the collection `collection`.
+	ApprovedTransfer {
+		collection: CollectionId,
+		item: ItemId,
+		owner: T::AccountId,
+		delegate: T::AccountId,
+	},
+	/// The approval to transfer the item `item` of the collection `collection` was cancelled.
+	ApprovalCancelled { collection: CollectionId, item: ItemId, owner: T::AccountId },
+	/// The item `item` of the collection `collection` can no longer be transferred.
+	ItemLocked { collection: CollectionId, item: ItemId },
+	/// The item `item` of the collection `collection` can be transferred again.
+	ItemUnlocked { collection: CollectionId, item: ItemId },
+	/// The item `item` of the collection `collection` is for sale at `price`, or no longer for
+	/// sale if there is none.
+	ItemPriceSet { collection: CollectionId, item: ItemId, price: Option<BalanceOf<T>> },
+	/// The item `item` of the collection `collection` was sold by `seller` to `buyer` for `price`,
+	/// of which `royalty` went to the owner of the collection.
+	ItemBought {
+		collection: CollectionId,
+		item: ItemId,
+		price: BalanceOf<T>,
+		royalty: BalanceOf<T>,
+		seller: T::AccountId,
+		buyer: T::AccountId,
+	},
+}
+
+/// The details of a collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionDetails<T: Config> {
+	/// The account which created the collection, and can mint and manage its items.
+	pub owner: T::AccountId,
+	/// The number of items in the collection.
+	pub items: u32,
+	/// The percentage of the price of the sales of items which goes to the owner.
+	pub royalty: u8,
+	/// Whether the items of the collection can be transferred by their owners.
+	pub transferable: bool,
+	/// The metadata of the collection.
+	pub metadata: Vec<u8>,
+}
+
+/// The details of an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemDetails<T: Config> {
+	/// The account owning the item.
+	pub owner: T::AccountId,
+	/// The account approved to transfer the item, if any.
+	pub approved: Option<T::AccountId>,
+	/// Whether the item is locked, so that it cannot be transferred.
+	pub locked: bool,
+	/// The price the item can be bought at, if it is for sale.
+	pub price: Option<BalanceOf<T>>,
+	/// The metadata of the item.
+	pub metadata: Vec<u8>,
+}
+
+/// This is the NFTs Module.
+/// It lets accounts create collections of non-fungible items. The owner of a collection mints
+/// its items, which their owners can then transfer, sell or burn.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The details of each collection.
+	collections: BTreeMap<CollectionId, CollectionDetails<T>>,
+	/// The identifier of the next collection.
+	next_collection: CollectionId,
+	/// The details of each item, by collection.
+	items: BTreeMap<(CollectionId, ItemId), ItemDetails<T>>,
+	/// The attributes of each item, by key.
+	attributes: BTreeMap<(CollectionId, ItemId), BTreeMap<Vec<u8>, Vec<u8>>>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the NFTs Module.
+	pub fn new() -> Self {
+		Self {
+			collections: BTreeMap::new(),
+			next_collection: 0,
+			items: BTreeMap::new(),
+			attributes: BTreeMap::new(),
+			events: Vec::new(),
+		}
+	}
+
+	/// Get the details of the collection `collection`.
+	pub fn collection(&self, collection: CollectionId) -> Option<&CollectionDetails<T>> {
+		self.collections.get(&collection)
+	}
+
+	/// Get the details of the item `item` of the collection `collection`.
+	pub fn item(&self, collection: CollectionId, item: ItemId) -> Option<&ItemDetails<T>> {
+		self.items.get(&(collection, item))
+	}
+
+	/// Get the attribute `key` of the item `item` of the collection `collection`.
+	pub fn attribute(&self, collection: CollectionId, item: ItemId, key: &[u8]) -> Option<&[u8]> {
+		self.attributes.get(&(collection, item))?.get(key).map(Vec::as_slice)
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// Get the details of the collection `collection`, checking that `who` owns it.
+	fn ensure_collection_owner(
+		&mut self,
+		collection: CollectionId,
+		who: &T::AccountId,
+	) -> Result<&mut CollectionDetails<T>, &'static str> {
+		let details = self.collections.get_mut(&collection).ok_or("Collection does not exist.")?;
+		if details.owner != *who {
+			return Err("Caller is not the owner of the collection.");
+		}
+		Ok(details)
+	}
+
+	/// Get the details of the item `item` of the collection `collection`, checking that the
+	/// collection is owned by `who`.
+	fn ensure_item_of_collection_owner(
+		&mut self,
+		collection: CollectionId,
+		item: ItemId,
+		who: &T::AccountId,
+	) -> Result<&mut ItemDetails<T>, &'static str> {
+		self.ensure_collection_owner(collection, who)?;
+		self.items.get_mut(&(collection, item)).ok_or("Item does not exist.")
+	}
+
+	/// Check that `data` fits in `STRING_LIMIT`.
+	fn ensure_length(data: &[u8]) -> DispatchResult {
+		if data.len() > T::STRING_LIMIT as usize {
+			return Err("Data is too long.");
+		}
+		Ok(())
+	}
+
+	/// Move the item `item` of the collection `collection` to `to`, if the collection allows it
+	/// and the item is not locked. Any approval and price of the item are cleared.
+	fn do_transfer(
+		&mut self,
+		collection: CollectionId,
+		item: ItemId,
+		to: T::AccountId,
+	) -> Result<T::AccountId, &'static str> {
+		let transferable = self
+			.collections
+			.get(&collection)
+			.ok_or("Collection does not exist.")?
+			.transferable;
+		if !transferable {
+			return Err("Items of this collection cannot be transferred.");
+		}
+		let details = self.items.get_mut(&(collection, item)).ok_or("Item does not exist.")?;
+		if details.locked {
+			return Err("Item is locked.");
+		}
+		details.approved = None;
+		details.price = None;
+		let from = core::mem::replace(&mut details.owner, to.clone());
+		self.deposit_event(Event::Transferred { collection, item, from: from.clone(), to });
+		Ok(from)
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Create a new collection owned by the caller.
+	pub fn create_collection(&mut self, caller: T::AccountId) -> DispatchResult {
+		let collection = self.next_collection;
+		self.next_collection = collection.checked_add(1).ok_or("Overflow")?;
+		let details = CollectionDetails {
+			owner: caller.clone(),
+			items: 0,
+			royalty: 0,
+			transferable: true,
+			metadata: Vec::new(),
+		};
+		self.collections.insert(collection, details);
+		self.deposit_event(Event::Created { collection, owner: caller });
+		Ok(())
+	}
+
+	/// Set the metadata of the collection `collection`. Only its owner can set it.
+	pub fn set_collection_metadata(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		data: Vec<u8>,
+	) -> DispatchResult {
+		Self::ensure_length(&data)?;
+		self.ensure_collection_owner(collection, &caller)?.metadata = data.clone();
+		self.deposit_event(Event::CollectionMetadataSet { collection, data });
+		Ok(())
+	}
+
+	/// Take `royalty` percent of the price of the sales of the items of the collection
+	/// `collection`. Only its owner can set it.
+	pub fn set_royalty(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		royalty: u8,
+	) -> DispatchResult {
+		if royalty > 100 {
+			return Err("Royalty is more than 100 percent.");
+		}
+		self.ensure_collection_owner(collection, &caller)?.royalty = royalty;
+		self.deposit_event(Event::RoyaltySet { collection, royalty });
+		Ok(())
+	}
+
+	/// Allow or forbid the transfer of the items of the collection `collection`. Only its owner
+	/// can change it.
+	pub fn set_transferable(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		transferable: bool,
+	) -> DispatchResult {
+		self.ensure_collection_owner(collection, &caller)?.transferable = transferable;
+		self.deposit_event(Event::TransferabilitySet { collection, transferable });
+		Ok(())
+	}
+
+	/// Mint the item `item` of the collection `collection` to `owner`. Only the owner of the
+	/// collection can mint items.
+	pub fn mint(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		owner: T::AccountId,
+	) -> DispatchResult {
+		if self.items.contains_key(&(collection, item)) {
+			return Err("Item already exists.");
+		}
+		let details = self.ensure_collection_owner(collection, &caller)?;
+		details.items = details.items.checked_add(1).ok_or("Overflow")?;
+		let item_details = ItemDetails {
+			owner: owner.clone(),
+			approved: None,
+			locked: false,
+			price: None,
+			metadata: Vec::new(),
+		};
+		self.items.insert((collection, item), item_details);
+		self.deposit_event(Event::Issued { collection, item, owner });
+		Ok(())
+	}
+
+	/// Set the metadata of the item `item` of the collection `collection`. Only the owner of the
+	/// collection can set it.
+	pub fn set_metadata(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		data: Vec<u8>,
+	) -> DispatchResult {
+		Self::ensure_length(&data)?;
+		self.ensure_item_of_collection_owner(collection, item, &caller)?.metadata = data.clone();
+		self.deposit_event(Event::MetadataSet { collection, item, data });
+		Ok(())
+	}
+
+	/// Set the attribute `key` of the item `item` of the collection `collection` to `value`. Only
+	/// the owner of the collection can set it.
+	pub fn set_attribute(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		key: Vec<u8>,
+		value: Vec<u8>,
+	) -> DispatchResult {
+		Self::ensure_length(&key)?;
+		Self::ensure_length(&value)?;
+		self.ensure_item_of_collection_owner(collection, item, &caller)?;
+		let attributes = self.attributes.entry((collection, item)).or_default();
+		if !attributes.contains_key(&key) && attributes.len() >= T::MAX_ATTRIBUTES as usize {
+			return Err("Too many attributes.");
+		}
+		attributes.insert(key.clone(), value.clone());
+		self.deposit_event(Event::AttributeSet { collection, item, key, value });
+		Ok(())
+	}
+
+	/// Clear the attribute `key` of the item `item` of the collection `collection`. Only the owner
+	/// of the collection can clear it.
+	pub fn clear_attribute(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		key: Vec<u8>,
+	) -> DispatchResult {
+		self.ensure_item_of_collection_owner(collection, item, &caller)?;
+		let attributes = self
+			.attributes
+			.get_mut(&(collection, item))
+			.ok_or("Attribute does not exist.")?;
+		attributes.remove(&key).ok_or("Attribute does not exist.")?;
+		if attributes.is_empty() {
+			self.attributes.remove(&(collection, item));
+		}
+		self.deposit_event(Event::AttributeCleared { collection, item, key });
+		Ok(())
+	}
+
+	/// Transfer the item `item` of the collection `collection` to `dest`. The caller must own the
+	/// item or be approved to transfer it.
+	pub fn transfer(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		dest: T::AccountId,
+	) -> DispatchResult {
+		let details = self.items.get(&(collection, item)).ok_or("Item does not exist.")?;
+		if details.owner != caller && details.approved.as_ref() != Some(&caller) {
+			return Err("Caller is not allowed to transfer the item.");
+		}
+		self.do_transfer(collection, item, dest)?;
+		Ok(())
+	}
+
+	/// Burn the item `item` of the collection `collection`, which the caller owns.
+	pub fn burn(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+	) -> DispatchResult {
+		let details = self.items.get(&(collection, item)).ok_or("Item does not exist.")?;
+		if details.owner != caller {
+			return Err("Caller is not the owner of the item.");
+		}
+		self.items.remove(&(collection, item));
+		self.attributes.remove(&(collection, item));
+		if let Some(details) = self.collections.get_mut(&collection) {
+			details.items -= 1;
+		}
+		self.deposit_event(Event::Burned { collection, item, owner: caller });
+		Ok(())
+	}
+
+	/// Approve `delegate` to transfer the item `item` of the collection `collection`, which the
+	/// caller owns, replacing any previous approval.
+	pub fn approve_transfer(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		delegate: T::AccountId,
+	) -> DispatchResult {
+		let details = self.items.get_mut(&(collection, item)).ok_or("Item does not exist.")?;
+		if details.owner != caller {
+			return Err("Caller is not the owner of the item.");
+		}
+		details.approved = Some(delegate.clone());
+		self.deposit_event(Event::ApprovedTransfer { collection, item, owner: caller, delegate });
+		Ok(())
+	}
+
+	/// Cancel the approval to transfer the item `item` of the collection `collection`, which the
+	/// caller owns.
+	pub fn cancel_approval(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+	) -> DispatchResult {
+		let details = self.items.get_mut(&(collection, item)).ok_or("Item does not exist.")?;
+		if details.owner != caller {
+			return Err("Caller is not the owner of the item.");
+		}
+		details.approved.take().ok_or("Approval does not exist.")?;
+		self.deposit_event(Event::ApprovalCancelled { collection, item, owner: caller });
+		Ok(())
+	}
+
+	/// Prevent the item `item` of the collection `collection` from being transferred. Only the
+	/// owner of the collection can lock its items.
+	pub fn lock_item(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+	) -> DispatchResult {
+		self.ensure_item_of_collection_owner(collection, item, &caller)?.locked = true;
+		self.deposit_event(Event::ItemLocked { collection, item });
+		Ok(())
+	}
+
+	/// Allow the item `item` of the collection `collection` to be transferred again. Only the
+	/// owner of the collection can unlock its items.
+	pub fn unlock_item(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+	) -> DispatchResult {
+		self.ensure_item_of_collection_owner(collection, item, &caller)?.locked = false;
+		self.deposit_event(Event::ItemUnlocked { collection, item });
+		Ok(())
+	}
+
+	/// Put the item `item` of the collection `collection`, which the caller owns, for sale at
+	/// `price`, or take it off sale if there is none.
+	pub fn set_price(
+		&mut self,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		price: Option<BalanceOf<T>>,
+	) -> DispatchResult {
+		let details = self.items.get_mut(&(collection, item)).ok_or("Item does not exist.")?;
+		if details.owner != caller {
+			return Err("Caller is not the owner of the item.");
+		}
+		details.price = price;
+		self.deposit_event(Event::ItemPriceSet { collection, item, price });
+		Ok(())
+	}
+
+	/// Buy the item `item` of the collection `collection`, if it is for sale for no more than
+	/// `bid_price`. The owner of the collection receives its royalty from the price.
+	pub fn buy_item(
+		runtime: &mut T,
+		caller: T::AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		bid_price: BalanceOf<T>,
+	) -> DispatchResult {
+		let nfts: &mut Self = runtime.as_mut();
+		let details = nfts.items.get(&(collection, item)).ok_or("Item does not exist.")?;
+		let price = details.price.ok_or("Item is not for sale.")?;
+		if bid_price < price {
+			return Err("Bid is lower than the price of the item.");
+		}
+		let seller = details.owner.clone();
+		let collection_details =
+			nfts.collections.get(&collection).ok_or("Collection does not exist.")?;
+		let collection_owner = collection_details.owner.clone();
+		let royalty = price.into().saturating_mul(u128::from(collection_details.royalty)) / 100;
+		let royalty = BalanceOf::<T>::try_from(royalty).unwrap_or_else(|_| BalanceOf::<T>::zero());
+		let payment = price.checked_sub(&royalty).ok_or("Overflow")?;
+
+		support::with_transaction(runtime, |runtime| {
+			let nfts: &mut Self = runtime.as_mut();
+			nfts.do_transfer(collection, item, caller.clone())?;
+			T::Currency::transfer(runtime, &caller, &seller, payment)?;
+			T::Currency::transfer(runtime, &caller, &collection_owner, royalty)
+		})?;
+
+		let nfts: &mut Self = runtime.as_mut();
+		nfts.deposit_event(Event::ItemBought {
+			collection,
+			item,
+			price,
+			royalty,
+			seller,
+			buyer: caller,
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Event;
+	use crate::{balances, nfts};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[event]
+		nfts: nfts::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = &'static str;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl nfts::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		const STRING_LIMIT: u32 = 8;
+		const MAX_ATTRIBUTES: u32 = 1;
+	}
+
+	crate::testing::impl_dispatch!(nfts);
+
+	/// Create the collection 0 owned by "creator", with the item 0 owned by "alice".
+	fn create_item(runtime: &mut Runtime) {
+		dispatch(runtime, "creator", nfts::Call::create_collection {}).unwrap();
+		let mint = nfts::Call::mint { collection: 0, item: 0, owner: "alice" };
+		dispatch(runtime, "creator", mint).unwrap();
+	}
+
+	#[test]
+	fn mint_with_attributes() {
+		let mut runtime = Runtime::new();
+		create_item(&mut runtime);
+		let mint = nfts::Call::mint { collection: 0, item: 0, owner: "bob" };
+		assert_eq!(dispatch(&mut runtime, "creator", mint), Err("Item already exists."));
+		let mint = nfts::Call::mint { collection: 0, item: 1, owner: "bob" };
+		assert_eq!(
+			dispatch(&mut runtime, "alice", mint),
+			Err("Caller is not the owner of the collection.")
+		);
+		assert_eq!(runtime.nfts.collection(0).unwrap().items, 1);
+
+		let set_metadata =
+			|data: &[u8]| nfts::Call::set_metadata { collection: 0, item: 0, data: data.to_vec() };
+		assert_eq!(
+			dispatch(&mut runtime, "creator", set_metadata(b"too long!")),
+			Err("Data is too long.")
+		);
+		dispatch(&mut runtime, "creator", set_metadata(b"ipfs://")).unwrap();
+		assert_eq!(runtime.nfts.item(0, 0).unwrap().metadata, b"ipfs://");
+
+		let set_attribute = |key: &[u8]| nfts::Call::set_attribute {
+			collection: 0,
+			item: 0,
+			key: key.to_vec(),
+			value: b"gold".to_vec(),
+		};
+		dispatch(&mut runtime, "creator", set_attribute(b"tier")).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, "creator", set_attribute(b"color")),
+			Err("Too many attributes.")
+		);
+		assert_eq!(runtime.nfts.attribute(0, 0, b"tier"), Some(&b"gold"[..]));
+		let clear_attribute =
+			nfts::Call::clear_attribute { collection: 0, item: 0, key: b"tier".to_vec() };
+		dispatch(&mut runtime, "creator", clear_attribute).unwrap();
+		assert_eq!(runtime.nfts.attribute(0, 0, b"tier"), None);
+
+		// Only the owner of an item can burn it.
+		let burn = nfts::Call::burn { collection: 0, item: 0 };
+		assert_eq!(
+			dispatch(&mut runtime, "creator", burn.clone()),
+			Err("Caller is not the owner of the item.")
+		);
+		dispatch(&mut runtime, "alice", burn).unwrap();
+		assert!(runtime.nfts.item(0, 0).is_none());
+		assert_eq!(runtime.nfts.collection(0).unwrap().items, 0);
+	}
+
+	#[test]
+	fn transfer_approve_and_lock() {
+		let mut runtime = Runtime::new();
+		create_item(&mut runtime);
+
+		let transfer = |dest| nfts::Call::transfer { collection: 0, item: 0, dest };
+		assert_eq!(
+			dispatch(&mut runtime, "bob", transfer("bob")),
+			Err("Caller is not allowed to transfer the item.")
+		);
+		let approve = nfts::Call::approve_transfer { collection: 0, item: 0, delegate: "bob" };
+		dispatch(&mut runtime, "alice", approve).unwrap();
+		dispatch(&mut runtime, "bob", transfer("charlie")).unwrap();
+		assert_eq!(runtime.nfts.item(0, 0).unwrap().owner, "charlie");
+		// The approval is cleared by the transfer.
+		assert_eq!(runtime.nfts.item(0, 0).unwrap().approved, None);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::nfts(Event::Transferred { from: "alice", to: "charlie", .. }))
+		));
+
+		// Locked items can't be transferred.
+		dispatch(&mut runtime, "creator", nfts::Call::lock_item { collection: 0, item: 0 })
+			.unwrap();
+		assert_eq!(dispatch(&mut runtime, "charlie", transfer("alice")), Err("Item is locked."));
+		dispatch(&mut runtime, "creator", nfts::Call::unlock_item { collection: 0, item: 0 })
+			.unwrap();
+
+		// Neither can the items of a collection which forbids transfers.
+		let set_transferable =
+			|transferable| nfts::Call::set_transferable { collection: 0, transferable };
+		dispatch(&mut runtime, "creator", set_transferable(false)).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, "charlie", transfer("alice")),
+			Err("Items of this collection cannot be transferred.")
+		);
+		dispatch(&mut runtime, "creator", set_transferable(true)).unwrap();
+		dispatch(&mut runtime, "charlie", transfer("alice")).unwrap();
+	}
+
+	#[test]
+	fn sales_pay_royalties() {
+		let mut runtime = Runtime::new();
+		create_item(&mut runtime);
+		runtime.balances.set_balance("bob", 150).unwrap();
+		assert_eq!(
+			dispatch(
+				&mut runtime,
+				"creator",
+				nfts::Call::set_royalty { collection: 0, royalty: 101 }
+			),
+			Err("Royalty is more than 100 percent.")
+		);
+		dispatch(&mut runtime, "creator", nfts::Call::set_royalty { collection: 0, royalty: 10 })
+			.unwrap();
+
+		let buy = |bid_price| nfts::Call::buy_item { collection: 0, item: 0, bid_price };
+		assert_eq!(dispatch(&mut runtime, "bob", buy(100)), Err("Item is not for sale."));
+		let set_price = |price| nfts::Call::set_price { collection: 0, item: 0, price };
+		dispatch(&mut runtime, "alice", set_price(Some(200))).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, "bob", buy(100)),
+			Err("Bid is lower than the price of the item.")
+		);
+
+		// Nothing changes if the buyer can't pay.
+		assert_eq!(dispatch(&mut runtime, "bob", buy(200)), Err("Not enough funds!"));
+		assert_eq!(runtime.nfts.item(0, 0).unwrap().owner, "alice");
+
+		dispatch(&mut runtime, "alice", set_price(Some(100))).unwrap();
+		dispatch(&mut runtime, "bob", buy(100)).unwrap();
+		assert_eq!(runtime.nfts.item(0, 0).unwrap().owner, "bob");
+		assert_eq!(runtime.nfts.item(0, 0).unwrap().price, None);
+		assert_eq!(runtime.balances.balance(&"bob"), 50);
+		assert_eq!(runtime.balances.balance(&"alice"), 90);
+		assert_eq!(runtime.balances.balance(&"creator"), 10);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::nfts(Event::ItemBought { price: 100, royalty: 10, .. }))
+		));
+	}
+}
@@ -1,87 +1,565 @@
 use core::fmt::Debug;
-use std::collections::BTreeMap;
+use num::{CheckedAdd, CheckedMul, Zero};
+use sha2::{Digest, Sha256};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	io,
+	ops::Bound,
+	path::Path,
+};
 
-use crate::support::DispatchResult;
+use crate::{
+	merkle::{self, MerkleProof},
+	support::{self, Currency, DispatchResult, ReservableCurrency, Time, H256},
+};
 
-pub trait Config: crate::system::Config {
+pub trait Config:
+	Sized + crate::system::Config + AsRef<crate::system::Pallet<Self>> + AsMut<Pallet<Self>>
+{
 	/// The type which represents the content that can be claimed using this pallet.
-	/// Could be the content directly as bytes, or better yet the hash of that content.
-	/// We leave that decision to the runtime developer.
-	type Content: Debug + Ord;
+	/// Could be the content directly as bytes, or better yet the hash of that content, as given
+	/// by `hash_content` and `hash_file`, so that documents of any size never have to be stored.
+	type Content: Debug + Ord + Clone;
+	/// The source of the time at which claims are created, if the runtime has one.
+	type Time: Time<Self>;
+	/// The currency the deposits of the claims are reserved in.
+	type Currency: ReservableCurrency<Self, Self::AccountId, Balance: CheckedMul + From<u32>>;
+	/// The maximum length of the note of a claim, in bytes.
+	const MAX_NOTE_LENGTH: u32;
+	/// The deposit reserved from the creator of a claim, until the claim is removed.
+	const CLAIM_DEPOSIT: BalanceOf<Self>;
+	/// The deposit reserved for each byte of the note of a claim, on top of `CLAIM_DEPOSIT`.
+	const NOTE_DEPOSIT_PER_BYTE: BalanceOf<Self>;
+}
+
+/// The type of the time at which a claim was created.
+pub type MomentOf<T> = <<T as Config>::Time as Time<T>>::Moment;
+/// The type of the deposits of the claims.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+/// Hash some `content` into the claim for it.
+pub fn hash_content(content: &[u8]) -> H256 {
+	support::sha2_256(content)
+}
+
+/// Hash the content of the file at `path` into the claim for it, without reading the whole file
+/// in memory.
+pub fn hash_file(path: impl AsRef<Path>) -> io::Result<H256> {
+	let mut hasher = Sha256::new();
+	io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+	Ok(hasher.finalize().into())
+}
+
+/// A claim on some content, and when it was made.
+#[derive(Debug, Clone)]
+pub struct Claim<T: Config> {
+	/// The account owning the claim.
+	pub owner: T::AccountId,
+	/// The block in which the claim was created.
+	pub block_number: T::BlockNumber,
+	/// The timestamp of that block, if the runtime keeps track of time.
+	pub timestamp: Option<MomentOf<T>>,
+	/// A description of the content given by the owner.
+	pub note: Option<String>,
+	/// The account the deposit is reserved from, which is the owner that created the claim. The
+	/// deposit stays with it when the claim is transferred.
+	pub depositor: T::AccountId,
+	/// The deposit reserved from the depositor.
+	pub deposit: BalanceOf<T>,
+	/// The block at the start of which the claim is removed, unless it is renewed, if any.
+	pub expires: Option<T::BlockNumber>,
 }
 
 /// This is the Proof of Existence Module.
 /// It is a simple module that allows accounts to claim existence of some data.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pallet<T: Config> {
-	/// A simple storage map from content to the owner of that content.
+	/// A simple storage map from content to the claim on that content.
 	/// Accounts can make multiple different claims, but each claim can only have one owner.
-	claims: BTreeMap<T::Content, T::AccountId>,
+	claims: BTreeMap<T::Content, Claim<T>>,
+	/// The content claimed by each account, to list the claims of an account.
+	claims_of: BTreeMap<T::AccountId, BTreeSet<T::Content>>,
+	/// The claims expiring at the start of each block.
+	expiring: BTreeMap<T::BlockNumber, BTreeSet<T::Content>>,
 }
 
 impl<T: Config> Pallet<T> {
 	/// Create a new instance of the Proof of Existence Module.
 	pub fn new() -> Self {
-		Self { claims: BTreeMap::new() }
+		Self { claims: BTreeMap::new(), claims_of: BTreeMap::new(), expiring: BTreeMap::new() }
 	}
 
 	/// Get the owner (if any) of a claim.
 	pub fn get_claim(&self, claim: &T::Content) -> Option<&T::AccountId> {
+		self.claims.get(claim).map(|claim| &claim.owner)
+	}
+
+	/// Get the claim (if any) on some content, with when it was created and its note.
+	pub fn claim(&self, claim: &T::Content) -> Option<&Claim<T>> {
 		self.claims.get(claim)
 	}
+
+	/// Check that the `document` hash is part of a batch of documents whose Merkle `root` was
+	/// claimed at or before block `at`, using its `proof`. Returns the claim on the batch.
+	/// Batches are built off chain with `merkle::MerkleTree`, over the `hash_content` of each
+	/// document, and their root is claimed with `create_claim` like any other content.
+	pub fn verify_batch(
+		&self,
+		root: &H256,
+		document: &H256,
+		proof: &MerkleProof,
+		at: T::BlockNumber,
+	) -> Result<&Claim<T>, &'static str>
+	where
+		T: Config<Content = H256>,
+	{
+		let claim = self.claims.get(root).ok_or("Claim does not exist.")?;
+		if claim.block_number > at {
+			return Err("The batch was claimed after this block.");
+		}
+		if !merkle::verify(root, document, proof) {
+			return Err("The document is not part of the batch.");
+		}
+		Ok(claim)
+	}
+
+	/// Get up to `limit` of the content claimed by `owner`, in order, starting after `start` if
+	/// given. To list all the claims of an account, pass the last content of each page as the
+	/// `start` of the next one.
+	pub fn claims_of(
+		&self,
+		owner: &T::AccountId,
+		start: Option<&T::Content>,
+		limit: usize,
+	) -> Vec<&T::Content> {
+		let Some(claims) = self.claims_of.get(owner) else { return Vec::new() };
+		let start = start.map_or(Bound::Unbounded, Bound::Excluded);
+		claims.range((start, Bound::Unbounded)).take(limit).collect()
+	}
+
+	/// The deposit of a claim with the given `note`.
+	pub fn claim_deposit(note: Option<&str>) -> Result<BalanceOf<T>, &'static str> {
+		let length = note.map_or(0, str::len);
+		let length = u32::try_from(length).map_err(|_| "Overflow")?;
+		T::NOTE_DEPOSIT_PER_BYTE
+			.checked_mul(&BalanceOf::<T>::from(length))
+			.and_then(|note_deposit| note_deposit.checked_add(&T::CLAIM_DEPOSIT))
+			.ok_or("Overflow")
+	}
+
+	/// Remove `claim`, releasing its deposit.
+	fn remove_claim(runtime: &mut T, claim: &T::Content) -> Option<Claim<T>> {
+		let poe: &mut Self = runtime.as_mut();
+		let removed = poe.claims.remove(claim)?;
+		poe.unindex_claim(&removed.owner, claim);
+		if let Some(expires) = removed.expires {
+			poe.unschedule_expiry(expires, claim);
+		}
+		T::Currency::unreserve(runtime, &removed.depositor, removed.deposit);
+		Some(removed)
+	}
+
+	/// Remove `claim` from the claims expiring at the start of block `expires`.
+	fn unschedule_expiry(&mut self, expires: T::BlockNumber, claim: &T::Content) {
+		if let Some(claims) = self.expiring.get_mut(&expires) {
+			claims.remove(claim);
+			if claims.is_empty() {
+				self.expiring.remove(&expires);
+			}
+		}
+	}
+
+	/// The block at which a claim made or renewed at block `now` with `lifetime` expires.
+	fn expiry(
+		now: T::BlockNumber,
+		lifetime: T::BlockNumber,
+	) -> Result<T::BlockNumber, &'static str> {
+		if lifetime.is_zero() {
+			return Err("The lifetime of a claim must not be zero.");
+		}
+		now.checked_add(&lifetime).ok_or("Overflow")
+	}
+
+	/// Add `claim` to the claims of `owner`.
+	fn index_claim(&mut self, owner: &T::AccountId, claim: T::Content) {
+		self.claims_of.entry(owner.clone()).or_default().insert(claim);
+	}
+
+	/// Remove `claim` from the claims of `owner`.
+	fn unindex_claim(&mut self, owner: &T::AccountId, claim: &T::Content) {
+		if let Some(claims) = self.claims_of.get_mut(owner) {
+			claims.remove(claim);
+			if claims.is_empty() {
+				self.claims_of.remove(owner);
+			}
+		}
+	}
 }
 
 #[macros::call]
 impl<T: Config> Pallet<T> {
-	/// Create a new claim on behalf of the `caller`.
-	/// This function will return an error if someone already has claimed that content.
-	pub fn create_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
-		if self.claims.contains_key(&claim) {
+	/// Create a new claim on behalf of the `caller`, with an optional `note` describing it.
+	/// This function will return an error if someone already has claimed that content, or if the
+	/// note is longer than `MAX_NOTE_LENGTH`. The deposit of the claim is reserved from the caller.
+	/// With a `lifetime`, the claim is removed that many blocks later, unless it is renewed.
+	pub fn create_claim(
+		runtime: &mut T,
+		caller: T::AccountId,
+		claim: T::Content,
+		note: Option<String>,
+		lifetime: Option<T::BlockNumber>,
+	) -> DispatchResult {
+		if note.as_ref().is_some_and(|note| note.len() > T::MAX_NOTE_LENGTH as usize) {
+			return Err("Note is too long.");
+		}
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		let block_number = system.block_number();
+		let expires = lifetime.map(|lifetime| Self::expiry(block_number, lifetime)).transpose()?;
+		let timestamp = T::Time::now(runtime);
+
+		let poe: &mut Self = runtime.as_mut();
+		if poe.claims.contains_key(&claim) {
 			return Err("This content is already claimed.");
 		}
-		self.claims.insert(claim, caller);
+		let deposit = Self::claim_deposit(note.as_deref())?;
+		T::Currency::reserve(runtime, &caller, deposit)?;
+
+		let poe: &mut Self = runtime.as_mut();
+		poe.index_claim(&caller, claim.clone());
+		if let Some(expires) = expires {
+			poe.expiring.entry(expires).or_default().insert(claim.clone());
+		}
+		let claim_info = Claim {
+			owner: caller.clone(),
+			block_number,
+			timestamp,
+			note,
+			depositor: caller,
+			deposit,
+			expires,
+		};
+		poe.claims.insert(claim, claim_info);
 		Ok(())
 	}
 
-	/// Revoke an existing claim on some content.
+	/// Revoke an existing claim on some content, releasing its deposit.
 	/// This function should only succeed if the caller is the owner of an existing claim.
 	/// It will return an error if the claim does not exist, or if the caller is not the owner.
-	pub fn revoke_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
-		let owner = self.get_claim(&claim).ok_or("Claim does not exist.")?;
+	pub fn revoke_claim(
+		runtime: &mut T,
+		caller: T::AccountId,
+		claim: T::Content,
+	) -> DispatchResult {
+		let poe: &mut Self = runtime.as_mut();
+		let owner = poe.get_claim(&claim).ok_or("Claim does not exist.")?;
 
 		if caller != *owner {
 			return Err("This content is owned by someone else.");
 		}
-		self.claims.remove(&claim);
+		Self::remove_claim(runtime, &claim);
 		Ok(())
 	}
+
+	/// Renew an existing expiring claim, so that it expires `lifetime` blocks from now instead.
+	/// This function should only succeed if the caller is the owner of an existing claim.
+	pub fn renew_claim(
+		runtime: &mut T,
+		caller: T::AccountId,
+		claim: T::Content,
+		lifetime: T::BlockNumber,
+	) -> DispatchResult {
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		let expires = Self::expiry(system.block_number(), lifetime)?;
+
+		let poe: &mut Self = runtime.as_mut();
+		let existing = poe.claims.get_mut(&claim).ok_or("Claim does not exist.")?;
+		if caller != existing.owner {
+			return Err("This content is owned by someone else.");
+		}
+		let previous = existing.expires.replace(expires).ok_or("Claim does not expire.")?;
+		poe.unschedule_expiry(previous, &claim);
+		poe.expiring.entry(expires).or_default().insert(claim);
+		Ok(())
+	}
+
+	/// Transfer an existing claim on some content to `new_owner`. The deposit of the claim stays
+	/// reserved from the account which created it, and goes back to it when the claim is removed.
+	/// This function should only succeed if the caller is the owner of an existing claim.
+	pub fn transfer_claim(
+		runtime: &mut T,
+		caller: T::AccountId,
+		claim: T::Content,
+		new_owner: T::AccountId,
+	) -> DispatchResult {
+		let poe: &mut Self = runtime.as_mut();
+		let existing = poe.claims.get_mut(&claim).ok_or("Claim does not exist.")?;
+		if caller != existing.owner {
+			return Err("This content is owned by someone else.");
+		}
+		existing.owner = new_owner.clone();
+		poe.unindex_claim(&caller, &claim);
+		poe.index_claim(&new_owner, claim);
+		Ok(())
+	}
+}
+
+impl<T: Config> support::Hooks<T, T::BlockNumber> for Pallet<T> {
+	/// Remove the claims expiring at the start of block `now`, releasing their deposits.
+	fn on_initialize(runtime: &mut T, now: T::BlockNumber) {
+		let poe: &mut Self = runtime.as_mut();
+		let expiring = poe.expiring.remove(&now).unwrap_or_default();
+		for claim in expiring {
+			Self::remove_claim(runtime, &claim);
+		}
+	}
 }
 
 #[cfg(test)]
 mod test {
-	struct TestConfig;
+	use super::hash_content;
+	use crate::{
+		balances,
+		merkle::MerkleTree,
+		proof_of_existence,
+		support::{InherentData, H256},
+		timestamp,
+	};
 
-	impl super::Config for TestConfig {
-		type Content = &'static str;
+	type ProofOfExistence = proof_of_existence::Pallet<Runtime>;
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		#[inherent]
+		timestamp: timestamp::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[hooks]
+		proof_of_existence: proof_of_existence::Pallet<Self>,
 	}
 
-	impl crate::system::Config for TestConfig {
+	impl crate::system::Config for Runtime {
 		type AccountId = &'static str;
 		type BlockNumber = u32;
 		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl timestamp::Config for Runtime {
+		type Moment = u64;
+		const MINIMUM_PERIOD: u64 = 5;
+		const MAXIMUM_DRIFT: u64 = 100;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl proof_of_existence::Config for Runtime {
+		type Content = H256;
+		type Time = timestamp::Pallet<Self>;
+		type Currency = balances::Pallet<Self>;
+		const MAX_NOTE_LENGTH: u32 = 16;
+		const CLAIM_DEPOSIT: u32 = 10;
+		const NOTE_DEPOSIT_PER_BYTE: u32 = 1;
+	}
+
+	fn new_runtime() -> Runtime {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		runtime.balances.set_balance("bob", 100).unwrap();
+		runtime
+	}
+
+	/// The timestamp of each block is 10 after the previous one.
+	fn inherent_data(runtime: &Runtime) -> InherentData {
+		let mut data = InherentData::new();
+		let now = 1_000 + u64::from(runtime.system.block_number()) * 10;
+		data.put_data(timestamp::INHERENT_IDENTIFIER, now);
+		data
 	}
 
+	crate::testing::impl_run_to_block!(inherent_data);
+
 	#[test]
 	fn basic_proof_of_existence() {
-		let mut poe = super::Pallet::<TestConfig>::new();
-		assert_eq!(poe.get_claim(&"Hello, world!"), None);
-		assert_eq!(poe.create_claim("alice", "Hello, world!"), Ok(()));
-		assert_eq!(poe.get_claim(&"Hello, world!"), Some(&"alice"));
+		let mut runtime = new_runtime();
+		let claim = hash_content(b"Hello, world!");
+		assert_eq!(runtime.proof_of_existence.get_claim(&claim), None);
+		assert_eq!(
+			ProofOfExistence::create_claim(&mut runtime, "alice", claim, None, None),
+			Ok(())
+		);
+		assert_eq!(runtime.proof_of_existence.get_claim(&claim), Some(&"alice"));
 		assert_eq!(
-			poe.create_claim("bob", "Hello, world!"),
+			ProofOfExistence::create_claim(&mut runtime, "bob", claim, None, None),
 			Err("This content is already claimed.")
 		);
-		assert_eq!(poe.revoke_claim("alice", "Hello, world!"), Ok(()));
-		assert_eq!(poe.create_claim("bob", "Hello, world!"), Ok(()));
+		assert_eq!(ProofOfExistence::revoke_claim(&mut runtime, "alice", claim), Ok(()));
+		assert_eq!(ProofOfExistence::create_claim(&mut runtime, "bob", claim, None, None), Ok(()));
+	}
+
+	#[test]
+	fn claim_metadata() {
+		let mut runtime = new_runtime();
+		let first = hash_content(b"first");
+		let second = hash_content(b"second");
+
+		// Before the first block, there is no timestamp yet.
+		ProofOfExistence::create_claim(&mut runtime, "alice", first, None, None).unwrap();
+		let claim = runtime.proof_of_existence.claim(&first).unwrap();
+		assert_eq!((claim.block_number, claim.timestamp), (0, None));
+
+		run_to_block(&mut runtime, 2);
+		let note = Some("Signed contract".to_string());
+		ProofOfExistence::create_claim(&mut runtime, "bob", second, note, None).unwrap();
+		let claim = runtime.proof_of_existence.claim(&second).unwrap();
+		assert_eq!(claim.owner, "bob");
+		assert_eq!((claim.block_number, claim.timestamp), (2, Some(1_010)));
+		assert_eq!(claim.note.as_deref(), Some("Signed contract"));
+
+		let note = Some("Way too long to be a note".to_string());
+		let result = ProofOfExistence::create_claim(&mut runtime, "bob", first, note, None);
+		assert_eq!(result, Err("Note is too long."));
+	}
+
+	#[test]
+	fn transfer_and_list_claims() {
+		let mut runtime = new_runtime();
+		let claims = (0u8..5).map(|i| hash_content(&[i])).collect::<Vec<_>>();
+		for claim in &claims {
+			ProofOfExistence::create_claim(&mut runtime, "alice", *claim, None, None).unwrap();
+		}
+		let mut sorted = claims.clone();
+		sorted.sort();
+
+		// The claims of an account are listed in pages.
+		let poe = &runtime.proof_of_existence;
+		let page = poe.claims_of(&"alice", None, 2);
+		assert_eq!(page, vec![&sorted[0], &sorted[1]]);
+		let page = poe.claims_of(&"alice", page.last().copied(), 2);
+		assert_eq!(page, vec![&sorted[2], &sorted[3]]);
+		let page = poe.claims_of(&"alice", page.last().copied(), 2);
+		assert_eq!(page, vec![&sorted[4]]);
+		assert!(poe.claims_of(&"bob", None, 10).is_empty());
+
+		// Only the owner can transfer a claim.
+		let result = ProofOfExistence::transfer_claim(&mut runtime, "bob", claims[0], "bob");
+		assert_eq!(result, Err("This content is owned by someone else."));
+		let unclaimed = hash_content(b"unclaimed");
+		let result = ProofOfExistence::transfer_claim(&mut runtime, "alice", unclaimed, "bob");
+		assert_eq!(result, Err("Claim does not exist."));
+		ProofOfExistence::transfer_claim(&mut runtime, "alice", claims[0], "bob").unwrap();
+		let poe = &runtime.proof_of_existence;
+		assert_eq!(poe.get_claim(&claims[0]), Some(&"bob"));
+		assert_eq!(poe.claims_of(&"bob", None, 10), vec![&claims[0]]);
+		assert_eq!(poe.claims_of(&"alice", None, 10).len(), 4);
+
+		// Revoked claims leave the index.
+		ProofOfExistence::revoke_claim(&mut runtime, "bob", claims[0]).unwrap();
+		assert!(runtime.proof_of_existence.claims_of(&"bob", None, 10).is_empty());
+	}
+
+	#[test]
+	fn claims_reserve_a_deposit() {
+		let mut runtime = new_runtime();
+		let first = hash_content(b"first");
+		let second = hash_content(b"second");
+
+		// The deposit grows with the note.
+		ProofOfExistence::create_claim(&mut runtime, "alice", first, None, None).unwrap();
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 10);
+		let note = Some("Contract".to_string());
+		ProofOfExistence::create_claim(&mut runtime, "alice", second, note, None).unwrap();
+		assert_eq!(runtime.proof_of_existence.claim(&second).unwrap().deposit, 18);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 28);
+
+		// The deposit stays with the creator of the claim, even once it is transferred.
+		ProofOfExistence::transfer_claim(&mut runtime, "alice", second, "bob").unwrap();
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 28);
+		assert_eq!(runtime.balances.reserved_balance(&"bob"), 0);
+		ProofOfExistence::revoke_claim(&mut runtime, "bob", second).unwrap();
+		assert_eq!(runtime.balances.balance(&"bob"), 100);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 10);
+		ProofOfExistence::revoke_claim(&mut runtime, "alice", first).unwrap();
+		assert_eq!(runtime.balances.balance(&"alice"), 100);
+
+		// Claims can not be made without the funds for their deposit.
+		let result = ProofOfExistence::create_claim(&mut runtime, "charlie", first, None, None);
+		assert_eq!(result, Err("Not enough funds!"));
+		assert_eq!(runtime.proof_of_existence.get_claim(&first), None);
+	}
+
+	#[test]
+	fn claims_expire_unless_renewed() {
+		let mut runtime = new_runtime();
+		let (first, second, forever) =
+			(hash_content(b"first"), hash_content(b"second"), hash_content(b"forever"));
+		run_to_block(&mut runtime, 1);
+
+		let result = ProofOfExistence::create_claim(&mut runtime, "alice", first, None, Some(0));
+		assert_eq!(result, Err("The lifetime of a claim must not be zero."));
+		ProofOfExistence::create_claim(&mut runtime, "alice", first, None, Some(3)).unwrap();
+		ProofOfExistence::create_claim(&mut runtime, "alice", second, None, Some(2)).unwrap();
+		ProofOfExistence::create_claim(&mut runtime, "alice", forever, None, None).unwrap();
+		assert_eq!(runtime.proof_of_existence.claim(&first).unwrap().expires, Some(4));
+
+		// Only the owner can renew a claim, and only if it expires.
+		run_to_block(&mut runtime, 2);
+		let result = ProofOfExistence::renew_claim(&mut runtime, "bob", second, 3);
+		assert_eq!(result, Err("This content is owned by someone else."));
+		let result = ProofOfExistence::renew_claim(&mut runtime, "alice", forever, 3);
+		assert_eq!(result, Err("Claim does not expire."));
+		ProofOfExistence::renew_claim(&mut runtime, "alice", second, 3).unwrap();
+		assert_eq!(runtime.proof_of_existence.claim(&second).unwrap().expires, Some(5));
+
+		// Expired claims are removed at the start of their block, releasing their deposit.
+		run_to_block(&mut runtime, 3);
+		assert!(runtime.proof_of_existence.get_claim(&second).is_some());
+		run_to_block(&mut runtime, 4);
+		assert_eq!(runtime.proof_of_existence.get_claim(&first), None);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 20);
+		run_to_block(&mut runtime, 5);
+		assert_eq!(runtime.proof_of_existence.get_claim(&second), None);
+		assert_eq!(runtime.proof_of_existence.claims_of(&"alice", None, 10), vec![&forever]);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 10);
+	}
+
+	#[test]
+	fn batch_notarization() {
+		let mut runtime = new_runtime();
+		let documents = (0..10u8).map(|i| hash_content(&[i])).collect::<Vec<_>>();
+		let tree = MerkleTree::new(&documents).unwrap();
+		let root = tree.root();
+		let proof = tree.proof(7).unwrap();
+
+		let result = runtime.proof_of_existence.verify_batch(&root, &documents[7], &proof, 5);
+		assert_eq!(result.err(), Some("Claim does not exist."));
+
+		// A single claim on the root notarizes the whole batch.
+		run_to_block(&mut runtime, 2);
+		let note = Some("Batch".to_string());
+		ProofOfExistence::create_claim(&mut runtime, "alice", root, note, None).unwrap();
+		let poe = &runtime.proof_of_existence;
+		let claim = poe.verify_batch(&root, &documents[7], &proof, 5).unwrap();
+		assert_eq!((claim.owner, claim.block_number), ("alice", 2));
+
+		let result = poe.verify_batch(&root, &documents[7], &proof, 1);
+		assert_eq!(result.err(), Some("The batch was claimed after this block."));
+		let result = poe.verify_batch(&root, &documents[6], &proof, 5);
+		assert_eq!(result.err(), Some("The document is not part of the batch."));
+	}
+
+	#[test]
+	fn hash_file() {
+		let path = std::env::temp_dir().join(format!("poe-{}.txt", std::process::id()));
+		std::fs::write(&path, b"Hello, world!").unwrap();
+		let claim = super::hash_file(&path);
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(claim.unwrap(), hash_content(b"Hello, world!"));
+		assert!(super::hash_file(&path).is_err());
 	}
 }
@@ -0,0 +1,634 @@
+use core::{fmt::Debug, hash::Hash};
+use std::collections::BTreeMap;
+
+use num::{CheckedAdd, Zero};
+
+use crate::{
+	support::{self, CallOf, Dispatch, DispatchResult, InstanceFilter, RawOrigin, H256},
+	system::OriginFor,
+};
+
+pub trait Config:
+	Sized
+	+ crate::system::Config
+	+ Dispatch<Caller = OriginFor<Self>, Call: Hash>
+	+ AsRef<crate::system::Pallet<Self>>
+	+ AsMut<Pallet<Self>>
+{
+	/// The permission scopes a proxy can be registered with, which filter the calls it can make.
+	type ProxyType: Clone + PartialEq + Debug + Hash + InstanceFilter<CallOf<Self>>;
+	/// The maximum number of proxies of a single account.
+	const MAX_PROXIES: u32;
+	/// The maximum number of announcements pending for a single proxy.
+	const MAX_PENDING: u32;
+}
+
+/// The events emitted by the Proxy Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// `delegate` was registered as a proxy of `delegator`.
+	ProxyAdded {
+		delegator: T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	},
+	/// `delegate` is no longer a proxy of `delegator`.
+	ProxyRemoved {
+		delegator: T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	},
+	/// `proxy` announced the call with `call_hash`, to be made on behalf of `real`.
+	Announced { real: T::AccountId, proxy: T::AccountId, call_hash: H256 },
+	/// A proxy made a call on behalf of another account, which returned `result`.
+	ProxyExecuted { result: DispatchResult },
+}
+
+/// The registration of an account as the proxy of another.
+#[derive(Debug, Clone)]
+pub struct ProxyDefinition<T: Config> {
+	/// The account which can make calls on behalf of the other.
+	pub delegate: T::AccountId,
+	/// The scope of the calls the proxy is allowed to make.
+	pub proxy_type: T::ProxyType,
+	/// The number of blocks a call must be announced for before the proxy can make it.
+	pub delay: T::BlockNumber,
+}
+
+impl<T: Config> ProxyDefinition<T> {
+	/// Whether this is the registration of `delegate` with the `proxy_type` and `delay`.
+	fn is(
+		&self,
+		delegate: &T::AccountId,
+		proxy_type: &T::ProxyType,
+		delay: T::BlockNumber,
+	) -> bool {
+		&self.delegate == delegate && &self.proxy_type == proxy_type && self.delay == delay
+	}
+}
+
+/// A call announced by a proxy, so that it can be made once the delay of the proxy has passed.
+#[derive(Debug, Clone)]
+pub struct Announcement<T: Config> {
+	/// The account the call will be made on behalf of.
+	pub real: T::AccountId,
+	/// The hash of the call.
+	pub call_hash: H256,
+	/// The block at which the call was announced.
+	pub height: T::BlockNumber,
+}
+
+/// This is the Proxy Module.
+/// It allows an account to register other accounts as its proxies, which can then make some calls
+/// on its behalf. A proxy registered with a delay must announce its calls first, which gives the
+/// account time to reject them.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The proxies registered by each account.
+	proxies: BTreeMap<T::AccountId, Vec<ProxyDefinition<T>>>,
+	/// The calls announced by each proxy.
+	announcements: BTreeMap<T::AccountId, Vec<Announcement<T>>>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Proxy Module.
+	pub fn new() -> Self {
+		Self { proxies: BTreeMap::new(), announcements: BTreeMap::new(), events: Vec::new() }
+	}
+
+	/// Get the proxies registered by `who`.
+	pub fn proxies(&self, who: &T::AccountId) -> &[ProxyDefinition<T>] {
+		self.proxies.get(who).map(|proxies| proxies.as_slice()).unwrap_or(&[])
+	}
+
+	/// Get the calls announced by the proxy `who`.
+	pub fn announcements(&self, who: &T::AccountId) -> &[Announcement<T>] {
+		self.announcements
+			.get(who)
+			.map(|announcements| announcements.as_slice())
+			.unwrap_or(&[])
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// Find a registration of `delegate` as a proxy of `real` which allows `call`, preferring the
+	/// one with the shortest delay, so that a proxy without delay never has to announce the call.
+	fn find_proxy(
+		&self,
+		real: &T::AccountId,
+		delegate: &T::AccountId,
+		call: &CallOf<T>,
+	) -> Result<&ProxyDefinition<T>, &'static str> {
+		let proxies = self
+			.proxies(real)
+			.iter()
+			.filter(|proxy| &proxy.delegate == delegate)
+			.collect::<Vec<_>>();
+		if proxies.is_empty() {
+			return Err("Not a proxy of this account.");
+		}
+		proxies
+			.into_iter()
+			.filter(|proxy| proxy.proxy_type.filter(call))
+			.min_by_key(|proxy| proxy.delay)
+			.ok_or("The proxy type does not allow this call.")
+	}
+
+	/// Remove the announcements of the proxy `delegate` which match `filter`, failing if there are
+	/// none.
+	fn remove_announcements(
+		&mut self,
+		delegate: &T::AccountId,
+		filter: impl Fn(&Announcement<T>) -> bool,
+	) -> DispatchResult {
+		let announcements =
+			self.announcements.get_mut(delegate).ok_or("Announcement does not exist.")?;
+		let count = announcements.len();
+		announcements.retain(|announcement| !filter(announcement));
+		if announcements.len() == count {
+			return Err("Announcement does not exist.");
+		}
+		if announcements.is_empty() {
+			self.announcements.remove(delegate);
+		}
+		Ok(())
+	}
+
+	/// Remove the announcements made by `delegate` on behalf of `real`, once it is no longer a
+	/// proxy of `real`, so that they cannot be made if it is registered again.
+	fn clear_announcements(&mut self, real: &T::AccountId, delegate: &T::AccountId) {
+		if let Some(announcements) = self.announcements.get_mut(delegate) {
+			announcements.retain(|announcement| announcement.real != *real);
+			if announcements.is_empty() {
+				self.announcements.remove(delegate);
+			}
+		}
+	}
+
+	/// Dispatch `call` on behalf of `real`, which a proxy is making it for.
+	fn do_proxy(runtime: &mut T, real: T::AccountId, call: CallOf<T>) {
+		let result = runtime.dispatch(RawOrigin::Signed(real), call);
+		let proxy: &mut Self = runtime.as_mut();
+		proxy.deposit_event(Event::ProxyExecuted { result });
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Register `delegate` as a proxy of the caller, which can make the calls allowed by
+	/// `proxy_type` on its behalf. If `delay` is not zero, the proxy must announce its calls that
+	/// many blocks before making them.
+	pub fn add_proxy(
+		&mut self,
+		caller: T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		if delegate == caller {
+			return Err("An account cannot be its own proxy.");
+		}
+		let proxies = self.proxies.entry(caller.clone()).or_default();
+		if proxies.iter().any(|proxy| proxy.is(&delegate, &proxy_type, delay)) {
+			return Err("Proxy already exists.");
+		}
+		if proxies.len() >= T::MAX_PROXIES as usize {
+			return Err("Too many proxies.");
+		}
+		proxies.push(ProxyDefinition {
+			delegate: delegate.clone(),
+			proxy_type: proxy_type.clone(),
+			delay,
+		});
+		self.deposit_event(Event::ProxyAdded { delegator: caller, delegate, proxy_type, delay });
+		Ok(())
+	}
+
+	/// Unregister `delegate` as a proxy of the caller with the `proxy_type` and `delay`. Once it is
+	/// no longer a proxy of the caller at all, its announcements for the caller are removed.
+	pub fn remove_proxy(
+		&mut self,
+		caller: T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		let proxies = self.proxies.get_mut(&caller).ok_or("Proxy does not exist.")?;
+		let index = proxies
+			.iter()
+			.position(|proxy| proxy.is(&delegate, &proxy_type, delay))
+			.ok_or("Proxy does not exist.")?;
+		proxies.remove(index);
+		let still_proxy = proxies.iter().any(|proxy| proxy.delegate == delegate);
+		if proxies.is_empty() {
+			self.proxies.remove(&caller);
+		}
+		if !still_proxy {
+			self.clear_announcements(&caller, &delegate);
+		}
+		self.deposit_event(Event::ProxyRemoved { delegator: caller, delegate, proxy_type, delay });
+		Ok(())
+	}
+
+	/// Unregister all the proxies of the caller, and remove their announcements for the caller.
+	pub fn remove_proxies(&mut self, caller: T::AccountId) -> DispatchResult {
+		for proxy in self.proxies.remove(&caller).unwrap_or_default() {
+			self.clear_announcements(&caller, &proxy.delegate);
+			self.deposit_event(Event::ProxyRemoved {
+				delegator: caller.clone(),
+				delegate: proxy.delegate,
+				proxy_type: proxy.proxy_type,
+				delay: proxy.delay,
+			});
+		}
+		Ok(())
+	}
+
+	/// Announce the call with `call_hash`, which the caller will make as a proxy of `real` once
+	/// the delay of the proxy has passed.
+	pub fn announce(
+		runtime: &mut T,
+		caller: T::AccountId,
+		real: T::AccountId,
+		call_hash: H256,
+	) -> DispatchResult {
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		let height = system.block_number();
+
+		let proxy: &mut Self = runtime.as_mut();
+		if !proxy.proxies(&real).iter().any(|proxy| proxy.delegate == caller) {
+			return Err("Not a proxy of this account.");
+		}
+		let announcements = proxy.announcements.entry(caller.clone()).or_default();
+		if announcements.len() >= T::MAX_PENDING as usize {
+			return Err("Too many announcements.");
+		}
+		announcements.push(Announcement { real: real.clone(), call_hash, height });
+		proxy.deposit_event(Event::Announced { real, proxy: caller, call_hash });
+		Ok(())
+	}
+
+	/// Remove the announcement of the call with `call_hash` made by the caller as a proxy of
+	/// `real`.
+	pub fn remove_announcement(
+		&mut self,
+		caller: T::AccountId,
+		real: T::AccountId,
+		call_hash: H256,
+	) -> DispatchResult {
+		self.remove_announcements(&caller, |announcement| {
+			announcement.real == real && announcement.call_hash == call_hash
+		})
+	}
+
+	/// Reject the announcement of the call with `call_hash` made by `delegate` as a proxy of the
+	/// caller, so that it can never be made.
+	pub fn reject_announcement(
+		&mut self,
+		caller: T::AccountId,
+		delegate: T::AccountId,
+		call_hash: H256,
+	) -> DispatchResult {
+		self.remove_announcements(&delegate, |announcement| {
+			announcement.real == caller && announcement.call_hash == call_hash
+		})
+	}
+
+	/// Make `call` on behalf of `real`, which the caller is a proxy of. The proxy must allow the
+	/// call, and must have no delay.
+	pub fn proxy(
+		runtime: &mut T,
+		caller: T::AccountId,
+		real: T::AccountId,
+		call: Box<CallOf<T>>,
+	) -> DispatchResult {
+		let proxy: &mut Self = runtime.as_mut();
+		let definition = proxy.find_proxy(&real, &caller, &call)?;
+		if !definition.delay.is_zero() {
+			return Err("This proxy must announce its calls first.");
+		}
+		Self::do_proxy(runtime, real, *call);
+		Ok(())
+	}
+
+	/// Make `call` on behalf of `real`, as announced by its proxy `delegate`. Anyone can make the
+	/// call once the delay of the proxy has passed since it was announced.
+	pub fn proxy_announced(
+		runtime: &mut T,
+		_caller: T::AccountId,
+		delegate: T::AccountId,
+		real: T::AccountId,
+		call: Box<CallOf<T>>,
+	) -> DispatchResult {
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		let now = system.block_number();
+		let call_hash = support::hash_of(&call);
+
+		let proxy: &mut Self = runtime.as_mut();
+		let delay = proxy.find_proxy(&real, &delegate, &call)?.delay;
+		let is_announced = |announcement: &Announcement<T>| {
+			announcement.real == real && announcement.call_hash == call_hash
+		};
+		let height = proxy
+			.announcements(&delegate)
+			.iter()
+			.filter(|announcement| is_announced(announcement))
+			.map(|announcement| announcement.height)
+			.min()
+			.ok_or("Announcement does not exist.")?;
+		if height.checked_add(&delay).is_none_or(|due| due > now) {
+			return Err("The delay of the announcement has not passed yet.");
+		}
+		proxy.remove_announcements(&delegate, is_announced)?;
+		Self::do_proxy(runtime, real, *call);
+		Ok(())
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Event;
+	use crate::{
+		balances, proof_of_existence, proxy,
+		support::{self, GetCallMetadata, InherentData, InstanceFilter},
+	};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		proof_of_existence: proof_of_existence::Pallet<Self>,
+		#[event]
+		proxy: proxy::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = &'static str;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl proof_of_existence::Config for Runtime {
+		type Content = support::H256;
+		type Time = ();
+		type Currency = balances::Pallet<Self>;
+		const MAX_NOTE_LENGTH: u32 = 16;
+		// Claims are free, so that only the proxies move the funds of alice.
+		const CLAIM_DEPOSIT: u32 = 0;
+		const NOTE_DEPOSIT_PER_BYTE: u32 = 0;
+	}
+
+	#[derive(Debug, Clone, PartialEq, Hash)]
+	enum ProxyType {
+		Any,
+		ProofOfExistence,
+	}
+
+	impl InstanceFilter<RuntimeCall> for ProxyType {
+		fn filter(&self, call: &RuntimeCall) -> bool {
+			match self {
+				ProxyType::Any => true,
+				ProxyType::ProofOfExistence => call.pallet_name() == "proof_of_existence",
+			}
+		}
+	}
+
+	impl proxy::Config for Runtime {
+		type ProxyType = ProxyType;
+		const MAX_PROXIES: u32 = 2;
+		const MAX_PENDING: u32 = 2;
+	}
+
+	fn next_block(runtime: &mut Runtime) {
+		let block = runtime.build_block(&InherentData::new(), vec![]);
+		runtime.execute_block(block).unwrap();
+	}
+
+	crate::testing::impl_dispatch!(proxy);
+
+	fn transfer(to: &'static str, amount: u32) -> RuntimeCall {
+		RuntimeCall::balances(balances::Call::transfer { to, amount })
+	}
+
+	fn create_claim(content: &str) -> RuntimeCall {
+		let claim = proof_of_existence::hash_content(content.as_bytes());
+		RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+			claim,
+			note: None,
+			lifetime: None,
+		})
+	}
+
+	#[test]
+	fn call_metadata() {
+		assert_eq!(transfer("bob", 10).pallet_name(), "balances");
+		assert_eq!(transfer("bob", 10).call_name(), "transfer");
+		assert_eq!(create_claim("hello").pallet_name(), "proof_of_existence");
+		assert_eq!(create_claim("hello").call_name(), "create_claim");
+	}
+
+	#[test]
+	fn add_and_remove_proxies() {
+		let mut runtime = Runtime::new();
+		let add = |delegate, proxy_type| proxy::Call::add_proxy { delegate, proxy_type, delay: 0 };
+
+		assert_eq!(dispatch(&mut runtime, "alice", add("bob", ProxyType::Any)), Ok(()));
+		assert_eq!(
+			dispatch(&mut runtime, "alice", add("bob", ProxyType::Any)),
+			Err("Proxy already exists.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "alice", add("alice", ProxyType::Any)),
+			Err("An account cannot be its own proxy.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "alice", add("bob", ProxyType::ProofOfExistence)),
+			Ok(())
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "alice", add("charlie", ProxyType::Any)),
+			Err("Too many proxies.")
+		);
+		assert_eq!(runtime.proxy.proxies(&"alice").len(), 2);
+
+		let remove =
+			proxy::Call::remove_proxy { delegate: "bob", proxy_type: ProxyType::Any, delay: 0 };
+		assert_eq!(dispatch(&mut runtime, "alice", remove.clone()), Ok(()));
+		assert_eq!(dispatch(&mut runtime, "alice", remove), Err("Proxy does not exist."));
+		assert_eq!(runtime.proxy.proxies(&"alice")[0].proxy_type, ProxyType::ProofOfExistence);
+
+		assert_eq!(dispatch(&mut runtime, "alice", proxy::Call::remove_proxies {}), Ok(()));
+		assert!(runtime.proxy.proxies(&"alice").is_empty());
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::proxy(Event::ProxyRemoved { delegate: "bob", .. }))
+		));
+	}
+
+	#[test]
+	fn proxy_calls_are_filtered() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		let add = proxy::Call::add_proxy {
+			delegate: "bob",
+			proxy_type: ProxyType::ProofOfExistence,
+			delay: 0,
+		};
+		dispatch(&mut runtime, "alice", add).unwrap();
+
+		// A proof of existence proxy can create claims on behalf of alice.
+		let call = proxy::Call::proxy { real: "alice", call: Box::new(create_claim("hello")) };
+		assert_eq!(dispatch(&mut runtime, "bob", call), Ok(()));
+		assert_eq!(
+			runtime
+				.proof_of_existence
+				.get_claim(&proof_of_existence::hash_content(b"hello")),
+			Some(&"alice")
+		);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::proxy(Event::ProxyExecuted { result: Ok(()) }))
+		));
+
+		// But it can never move her funds.
+		let call = proxy::Call::proxy { real: "alice", call: Box::new(transfer("bob", 10)) };
+		assert_eq!(
+			dispatch(&mut runtime, "bob", call),
+			Err("The proxy type does not allow this call.")
+		);
+		assert_eq!(runtime.balances.balance(&"alice"), 100);
+
+		let call = proxy::Call::proxy { real: "alice", call: Box::new(create_claim("world")) };
+		assert_eq!(dispatch(&mut runtime, "charlie", call), Err("Not a proxy of this account."));
+	}
+
+	#[test]
+	fn announced_proxy_calls() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		next_block(&mut runtime);
+		let add = proxy::Call::add_proxy { delegate: "bob", proxy_type: ProxyType::Any, delay: 2 };
+		dispatch(&mut runtime, "alice", add).unwrap();
+
+		// A proxy with a delay must announce its calls first.
+		let call = proxy::Call::proxy { real: "alice", call: Box::new(transfer("bob", 10)) };
+		assert_eq!(
+			dispatch(&mut runtime, "bob", call),
+			Err("This proxy must announce its calls first.")
+		);
+
+		let call_hash = support::hash_of(&transfer("bob", 10));
+		let announce = proxy::Call::announce { real: "alice", call_hash };
+		dispatch(&mut runtime, "bob", announce).unwrap();
+		assert_eq!(runtime.proxy.announcements(&"bob")[0].height, 1);
+
+		// Anyone can make the announced call, once the delay has passed.
+		let proxy_announced = proxy::Call::proxy_announced {
+			delegate: "bob",
+			real: "alice",
+			call: Box::new(transfer("bob", 10)),
+		};
+		next_block(&mut runtime);
+		assert_eq!(
+			dispatch(&mut runtime, "charlie", proxy_announced.clone()),
+			Err("The delay of the announcement has not passed yet.")
+		);
+		next_block(&mut runtime);
+		assert_eq!(dispatch(&mut runtime, "charlie", proxy_announced.clone()), Ok(()));
+		assert_eq!(runtime.balances.balance(&"bob"), 10);
+		assert!(runtime.proxy.announcements(&"bob").is_empty());
+		assert_eq!(
+			dispatch(&mut runtime, "charlie", proxy_announced.clone()),
+			Err("Announcement does not exist.")
+		);
+
+		// Alice can reject the calls announced by her proxy.
+		dispatch(&mut runtime, "bob", proxy::Call::announce { real: "alice", call_hash }).unwrap();
+		let reject = proxy::Call::reject_announcement { delegate: "bob", call_hash };
+		assert_eq!(dispatch(&mut runtime, "alice", reject), Ok(()));
+		next_block(&mut runtime);
+		next_block(&mut runtime);
+		assert_eq!(
+			dispatch(&mut runtime, "charlie", proxy_announced),
+			Err("Announcement does not exist.")
+		);
+		assert_eq!(runtime.balances.balance(&"bob"), 10);
+	}
+
+	#[test]
+	fn proxies_without_delay_are_preferred() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		for (proxy_type, delay) in [(ProxyType::Any, 2), (ProxyType::ProofOfExistence, 0)] {
+			let add = proxy::Call::add_proxy { delegate: "bob", proxy_type, delay };
+			dispatch(&mut runtime, "alice", add).unwrap();
+		}
+
+		// Both proxies allow the claim, but only the one with a delay allows the transfer.
+		let call = proxy::Call::proxy { real: "alice", call: Box::new(create_claim("hello")) };
+		assert_eq!(dispatch(&mut runtime, "bob", call), Ok(()));
+		let call = proxy::Call::proxy { real: "alice", call: Box::new(transfer("bob", 10)) };
+		assert_eq!(
+			dispatch(&mut runtime, "bob", call),
+			Err("This proxy must announce its calls first.")
+		);
+	}
+
+	#[test]
+	fn removed_proxies_lose_their_announcements() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		let add = proxy::Call::add_proxy { delegate: "bob", proxy_type: ProxyType::Any, delay: 2 };
+		let call_hash = support::hash_of(&transfer("bob", 10));
+		let announce = proxy::Call::announce { real: "alice", call_hash };
+		let proxy_announced = proxy::Call::proxy_announced {
+			delegate: "bob",
+			real: "alice",
+			call: Box::new(transfer("bob", 10)),
+		};
+
+		for remove in [
+			proxy::Call::remove_proxy { delegate: "bob", proxy_type: ProxyType::Any, delay: 2 },
+			proxy::Call::remove_proxies {},
+		] {
+			dispatch(&mut runtime, "alice", add.clone()).unwrap();
+			dispatch(&mut runtime, "bob", announce.clone()).unwrap();
+			dispatch(&mut runtime, "alice", remove).unwrap();
+			assert!(runtime.proxy.announcements(&"bob").is_empty());
+
+			// Registering the proxy again does not bring back its old announcements.
+			dispatch(&mut runtime, "alice", add.clone()).unwrap();
+			next_block(&mut runtime);
+			next_block(&mut runtime);
+			assert_eq!(
+				dispatch(&mut runtime, "charlie", proxy_announced.clone()),
+				Err("Announcement does not exist.")
+			);
+			dispatch(&mut runtime, "alice", proxy::Call::remove_proxies {}).unwrap();
+		}
+		assert_eq!(runtime.balances.balance(&"bob"), 0);
+	}
+}
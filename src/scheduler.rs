@@ -0,0 +1,574 @@
+use core::fmt::Debug;
+use std::collections::BTreeMap;
+
+use num::{CheckedAdd, One, Zero};
+
+use crate::{
+	support::{
+		self, CallOf, Currency, Dispatch, DispatchResult, RawOrigin, ReservableCurrency, Weight,
+	},
+	system::OriginFor,
+};
+
+/// The type of the deposits of the tasks.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config:
+	Sized
+	+ crate::system::Config
+	+ Dispatch<Caller = OriginFor<Self>, Call: Clone + Debug>
+	+ AsRef<crate::system::Pallet<Self>>
+	+ AsMut<Pallet<Self>>
+{
+	/// The currency the deposits of the tasks are reserved in.
+	type Currency: ReservableCurrency<Self, Self::AccountId>;
+	/// The type used to name tasks, so they can be looked up and cancelled later.
+	type TaskName: Ord + Clone + Debug;
+	/// The maximum weight of all the tasks dispatched in a single block. Tasks which do not fit
+	/// are postponed to the next block.
+	const MAXIMUM_WEIGHT: Weight;
+	/// The weight of dispatching a single task.
+	const TASK_WEIGHT: Weight;
+	/// The maximum number of tasks in the agenda of a single block. Must not be zero.
+	const MAX_SCHEDULED_PER_BLOCK: u32;
+	/// The deposit reserved from a signed origin for each of its tasks, until the task is done or
+	/// cancelled.
+	const TASK_DEPOSIT: BalanceOf<Self>;
+}
+
+/// A call scheduled to be dispatched at some future block.
+#[derive(Debug, Clone)]
+pub struct Scheduled<T: Config> {
+	/// The name of the task, if it was scheduled with one.
+	pub name: Option<T::TaskName>,
+	/// The origin which scheduled the task, and which the call is dispatched with.
+	pub origin: OriginFor<T>,
+	/// The call to dispatch.
+	pub call: CallOf<T>,
+	/// The number of blocks after which the task repeats, if it is periodic.
+	pub maybe_periodic: Option<T::BlockNumber>,
+	/// The block the task was scheduled at. A postponed task keeps it, so that a periodic task
+	/// repeats from it rather than from the block it was postponed to.
+	pub slot: T::BlockNumber,
+	/// The deposit reserved from the origin, if it is signed.
+	pub deposit: BalanceOf<T>,
+}
+
+/// This is the Scheduler Module.
+/// It allows an origin to schedule a call to be dispatched on its behalf at some future block,
+/// optionally repeating every few blocks. Signed origins reserve a deposit for each of their
+/// tasks.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The tasks scheduled at each block. Cancelled tasks leave an empty slot behind, so that the
+	/// index of the other tasks of the block does not change.
+	agenda: BTreeMap<T::BlockNumber, Vec<Option<Scheduled<T>>>>,
+	/// The block and index in the agenda of each named task.
+	lookup: BTreeMap<T::TaskName, (T::BlockNumber, u32)>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Scheduler Module.
+	pub fn new() -> Self {
+		Self { agenda: BTreeMap::new(), lookup: BTreeMap::new() }
+	}
+
+	/// Get the tasks scheduled at block `when`.
+	pub fn agenda(&self, when: &T::BlockNumber) -> &[Option<Scheduled<T>>] {
+		self.agenda.get(when).map(|tasks| tasks.as_slice()).unwrap_or(&[])
+	}
+
+	/// Get the block and index in the agenda of the task named `name`, if it is scheduled.
+	pub fn lookup(&self, name: &T::TaskName) -> Option<&(T::BlockNumber, u32)> {
+		self.lookup.get(name)
+	}
+
+	/// Whether the agenda of block `when` is full. The empty slots of cancelled tasks count too.
+	fn is_full(&self, when: &T::BlockNumber) -> bool {
+		self.agenda(when).len() as u32 >= T::MAX_SCHEDULED_PER_BLOCK
+	}
+
+	/// Place `task` in the agenda of the first block from `when` which is not full, and return
+	/// that block. If there is no such block, `task` is given back.
+	fn place_task(
+		&mut self,
+		mut when: T::BlockNumber,
+		task: Scheduled<T>,
+	) -> Result<T::BlockNumber, Scheduled<T>> {
+		while self.is_full(&when) {
+			match when.checked_add(&T::BlockNumber::one()) {
+				Some(next) => when = next,
+				None => return Err(task),
+			}
+		}
+		let tasks = self.agenda.entry(when).or_default();
+		let index = tasks.len() as u32;
+		if let Some(name) = &task.name {
+			self.lookup.insert(name.clone(), (when, index));
+		}
+		tasks.push(Some(task));
+		Ok(when)
+	}
+
+	/// Forget `task`, which is done, cancelled or cannot be placed, and return its deposit.
+	fn remove_task(runtime: &mut T, task: Scheduled<T>) {
+		let scheduler: &mut Self = runtime.as_mut();
+		if let Some(name) = &task.name {
+			scheduler.lookup.remove(name);
+		}
+		if let RawOrigin::Signed(who) = &task.origin {
+			T::Currency::unreserve(runtime, who, task.deposit);
+		}
+	}
+
+	/// The first run after block `now` of a task repeating every `period` blocks from `slot`, if
+	/// the block number does not overflow before it.
+	fn next_run(
+		slot: T::BlockNumber,
+		period: T::BlockNumber,
+		now: T::BlockNumber,
+	) -> Option<T::BlockNumber> {
+		let mut when = slot.checked_add(&period)?;
+		while when <= now {
+			when = when.checked_add(&period)?;
+		}
+		Some(when)
+	}
+
+	/// Schedule `call` at block `when` on behalf of `origin`, checking that block is in the future
+	/// and not full. Other pallets use this to dispatch calls later with an origin of their own,
+	/// like root.
+	pub fn do_schedule(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		name: Option<T::TaskName>,
+		when: T::BlockNumber,
+		maybe_periodic: Option<T::BlockNumber>,
+		call: CallOf<T>,
+	) -> DispatchResult {
+		if origin == RawOrigin::None {
+			return Err("Bad origin: tasks must be scheduled by some origin.");
+		}
+		if maybe_periodic.is_some_and(|period| period.is_zero()) {
+			return Err("The period of a task must not be zero.");
+		}
+
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		if when <= system.block_number() {
+			return Err("Target block number is in the past.");
+		}
+
+		let scheduler: &mut Self = runtime.as_mut();
+		if let Some(name) = &name {
+			if scheduler.lookup.contains_key(name) {
+				return Err("A task with this name is already scheduled.");
+			}
+		}
+		if scheduler.is_full(&when) {
+			return Err("The agenda of the block is full.");
+		}
+
+		let deposit = match &origin {
+			RawOrigin::Signed(who) => {
+				T::Currency::reserve(runtime, who, T::TASK_DEPOSIT)?;
+				T::TASK_DEPOSIT
+			},
+			_ => BalanceOf::<T>::zero(),
+		};
+		let task = Scheduled { name, origin, call, maybe_periodic, slot: when, deposit };
+		let scheduler: &mut Self = runtime.as_mut();
+		// The agenda of the block is not full, so the task is placed at `when`.
+		let _ = scheduler.place_task(when, task);
+		Ok(())
+	}
+
+	/// Cancel the task at `index` in the agenda of block `when` on behalf of `origin`.
+	/// Only the origin which scheduled a task, or root, can cancel it.
+	fn do_cancel(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		when: T::BlockNumber,
+		index: u32,
+	) -> DispatchResult {
+		let scheduler: &mut Self = runtime.as_mut();
+		let slot = scheduler
+			.agenda
+			.get_mut(&when)
+			.and_then(|tasks| tasks.get_mut(index as usize))
+			.filter(|slot| slot.is_some())
+			.ok_or("Task does not exist.")?;
+
+		if let Some(task) = slot {
+			if origin != RawOrigin::Root && origin != task.origin {
+				return Err("This task was scheduled by someone else.");
+			}
+		}
+
+		if let Some(task) = slot.take() {
+			Self::remove_task(runtime, task);
+		}
+		Ok(())
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Schedule `call` to be dispatched at block `when` on behalf of `origin`. If `maybe_periodic`
+	/// is given, the task is repeated every that many blocks until it is cancelled.
+	pub fn schedule(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		when: T::BlockNumber,
+		maybe_periodic: Option<T::BlockNumber>,
+		call: Box<CallOf<T>>,
+	) -> DispatchResult {
+		Self::do_schedule(runtime, origin, None, when, maybe_periodic, *call)
+	}
+
+	/// Cancel the task at `index` in the agenda of block `when`, returning its deposit.
+	/// Only the origin which scheduled a task, or root, can cancel it.
+	pub fn cancel(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		when: T::BlockNumber,
+		index: u32,
+	) -> DispatchResult {
+		Self::do_cancel(runtime, origin, when, index)
+	}
+
+	/// Schedule a task like `schedule`, with a `name` which can be used to cancel it later.
+	/// This function will return an error if a task with the same name is already scheduled.
+	pub fn schedule_named(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		name: T::TaskName,
+		when: T::BlockNumber,
+		maybe_periodic: Option<T::BlockNumber>,
+		call: Box<CallOf<T>>,
+	) -> DispatchResult {
+		Self::do_schedule(runtime, origin, Some(name), when, maybe_periodic, *call)
+	}
+
+	/// Cancel the task named `name`, returning its deposit.
+	/// Only the origin which scheduled a task, or root, can cancel it.
+	pub fn cancel_named(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		name: T::TaskName,
+	) -> DispatchResult {
+		let scheduler: &mut Self = runtime.as_mut();
+		let (when, index) = *scheduler.lookup.get(&name).ok_or("Task does not exist.")?;
+		Self::do_cancel(runtime, origin, when, index)
+	}
+}
+
+impl<T: Config> support::Hooks<T, T::BlockNumber> for Pallet<T> {
+	/// Dispatch the tasks scheduled at block `now`, as long as they fit in `MAXIMUM_WEIGHT`.
+	/// The tasks which do not fit are postponed to the next block which is not full, and periodic
+	/// tasks are scheduled again, counting their period from the block they were scheduled at.
+	/// Tasks which cannot be placed before the maximum block number are dropped, and their
+	/// deposit returned.
+	fn on_initialize(runtime: &mut T, now: T::BlockNumber) {
+		let scheduler: &mut Self = runtime.as_mut();
+		let tasks = scheduler.agenda.remove(&now).unwrap_or_default();
+		let next_block = now.checked_add(&T::BlockNumber::one());
+
+		let mut weight: Weight = 0;
+		for (index, task) in tasks.into_iter().enumerate() {
+			let Some(mut task) = task else { continue };
+			let scheduler: &mut Self = runtime.as_mut();
+
+			if weight.saturating_add(T::TASK_WEIGHT) > T::MAXIMUM_WEIGHT {
+				let postponed = match next_block {
+					Some(next_block) => scheduler.place_task(next_block, task),
+					None => Err(task),
+				};
+				if let Err(task) = postponed {
+					Self::remove_task(runtime, task);
+				}
+				continue;
+			}
+			weight += T::TASK_WEIGHT;
+
+			let result = runtime.dispatch(task.origin.clone(), task.call.clone());
+			if let Err(e) = result {
+				eprintln!(
+					"Scheduled Task Error\n\tBlock Number: {:?}\n\tTask Index: {}\n\tError: {}",
+					now, index, e
+				);
+			}
+
+			let scheduler: &mut Self = runtime.as_mut();
+			let rescheduled = match task.maybe_periodic {
+				// A periodic task which would overflow the block number simply stops repeating.
+				Some(period) => match Self::next_run(task.slot, period, now) {
+					Some(when) => {
+						task.slot = when;
+						scheduler.place_task(when, task)
+					},
+					None => Err(task),
+				},
+				None => Err(task),
+			};
+			if let Err(task) = rescheduled {
+				Self::remove_task(runtime, task);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		balances, scheduler,
+		support::{RawOrigin, Weight},
+	};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[hooks]
+		scheduler: scheduler::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = &'static str;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl scheduler::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		type TaskName = &'static str;
+		const MAXIMUM_WEIGHT: Weight = 20;
+		const TASK_WEIGHT: Weight = 10;
+		const MAX_SCHEDULED_PER_BLOCK: u32 = 3;
+		const TASK_DEPOSIT: u32 = 5;
+	}
+
+	crate::testing::impl_run_to_block!();
+
+	fn transfer(to: &'static str, amount: u32) -> Box<RuntimeCall> {
+		Box::new(RuntimeCall::balances(balances::Call::transfer { to, amount }))
+	}
+
+	#[test]
+	fn schedule_task() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+
+		assert_eq!(
+			scheduler::Pallet::schedule(
+				&mut runtime,
+				RawOrigin::Signed("alice"),
+				3,
+				None,
+				transfer("bob", 10)
+			),
+			Ok(())
+		);
+		assert_eq!(runtime.scheduler.agenda(&3).len(), 1);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 5);
+
+		// The task is dispatched on behalf of alice, when block 3 starts, and her deposit is
+		// returned.
+		run_to_block(&mut runtime, 2);
+		assert_eq!(runtime.balances.balance(&"bob"), 0);
+		run_to_block(&mut runtime, 3);
+		assert_eq!(runtime.balances.balance(&"alice"), 90);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 0);
+		assert_eq!(runtime.balances.balance(&"bob"), 10);
+		assert!(runtime.scheduler.agenda(&3).is_empty());
+
+		// Tasks can only be scheduled in the future, and by some origin.
+		assert_eq!(
+			scheduler::Pallet::schedule(
+				&mut runtime,
+				RawOrigin::Signed("alice"),
+				3,
+				None,
+				transfer("bob", 10)
+			),
+			Err("Target block number is in the past.")
+		);
+		assert!(scheduler::Pallet::schedule(
+			&mut runtime,
+			RawOrigin::None,
+			5,
+			None,
+			transfer("bob", 10)
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn periodic_named_task() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+
+		assert_eq!(
+			scheduler::Pallet::schedule_named(
+				&mut runtime,
+				RawOrigin::Signed("alice"),
+				"rent",
+				2,
+				Some(3),
+				transfer("bob", 10)
+			),
+			Ok(())
+		);
+		assert_eq!(runtime.scheduler.lookup(&"rent"), Some(&(2, 0)));
+
+		// A name can only be used by a single task.
+		assert_eq!(
+			scheduler::Pallet::schedule_named(
+				&mut runtime,
+				RawOrigin::Signed("alice"),
+				"rent",
+				4,
+				None,
+				transfer("bob", 10)
+			),
+			Err("A task with this name is already scheduled.")
+		);
+
+		// The task repeats at blocks 2, 5 and 8.
+		run_to_block(&mut runtime, 8);
+		assert_eq!(runtime.balances.balance(&"bob"), 30);
+		assert_eq!(runtime.scheduler.lookup(&"rent"), Some(&(11, 0)));
+
+		// Only the origin which scheduled the task, or root, can cancel it.
+		assert_eq!(
+			scheduler::Pallet::cancel_named(&mut runtime, RawOrigin::Signed("bob"), "rent"),
+			Err("This task was scheduled by someone else.")
+		);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 5);
+		let origin = RawOrigin::Signed("alice");
+		assert_eq!(scheduler::Pallet::cancel_named(&mut runtime, origin, "rent"), Ok(()));
+		assert_eq!(runtime.scheduler.lookup(&"rent"), None);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 0);
+		assert_eq!(
+			scheduler::Pallet::cancel_named(&mut runtime, RawOrigin::Root, "rent"),
+			Err("Task does not exist.")
+		);
+
+		run_to_block(&mut runtime, 11);
+		assert_eq!(runtime.balances.balance(&"bob"), 30);
+	}
+
+	#[test]
+	fn cancel_task() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+
+		for amount in [1, 2] {
+			let call = transfer("bob", amount);
+			let origin = RawOrigin::Signed("alice");
+			assert_eq!(scheduler::Pallet::schedule(&mut runtime, origin, 2, None, call), Ok(()));
+		}
+
+		// Cancelling a task does not change the index of the others.
+		assert_eq!(scheduler::Pallet::cancel(&mut runtime, RawOrigin::Root, 2, 0), Ok(()));
+		assert_eq!(
+			scheduler::Pallet::cancel(&mut runtime, RawOrigin::Root, 2, 0),
+			Err("Task does not exist.")
+		);
+		assert!(runtime.scheduler.agenda(&2)[1].is_some());
+
+		run_to_block(&mut runtime, 2);
+		assert_eq!(runtime.balances.balance(&"bob"), 2);
+	}
+
+	#[test]
+	fn tasks_limited_by_weight() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+
+		// Only two tasks fit in the weight of a block.
+		for _ in 0..3 {
+			let call = transfer("bob", 10);
+			let origin = RawOrigin::Signed("alice");
+			assert_eq!(scheduler::Pallet::schedule(&mut runtime, origin, 1, None, call), Ok(()));
+		}
+
+		run_to_block(&mut runtime, 1);
+		assert_eq!(runtime.balances.balance(&"bob"), 20);
+		assert_eq!(runtime.scheduler.agenda(&2).len(), 1);
+
+		// The third task is postponed to the next block.
+		run_to_block(&mut runtime, 2);
+		assert_eq!(runtime.balances.balance(&"bob"), 30);
+	}
+
+	#[test]
+	fn tasks_at_the_last_block() {
+		use crate::support::Hooks;
+
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		for name in ["first", "second", "third"] {
+			let call = transfer("bob", 10);
+			let origin = RawOrigin::Signed("alice");
+			scheduler::Pallet::schedule_named(&mut runtime, origin, name, u32::MAX, None, call)
+				.unwrap();
+		}
+
+		// There is no block to postpone the third task to, so it is dropped.
+		scheduler::Pallet::on_initialize(&mut runtime, u32::MAX);
+		assert_eq!(runtime.balances.balance(&"bob"), 20);
+		assert_eq!(runtime.scheduler.lookup(&"third"), None);
+		assert_eq!(runtime.balances.reserved_balance(&"alice"), 0);
+	}
+
+	#[test]
+	fn full_agendas() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		for when in [1, 1, 1, 2, 2, 2] {
+			let call = transfer("bob", 10);
+			let origin = RawOrigin::Signed("alice");
+			assert_eq!(scheduler::Pallet::schedule(&mut runtime, origin, when, None, call), Ok(()));
+		}
+
+		// No more tasks can be scheduled in a full block.
+		let call = transfer("bob", 10);
+		assert_eq!(
+			scheduler::Pallet::schedule(&mut runtime, RawOrigin::Root, 2, None, call),
+			Err("The agenda of the block is full.")
+		);
+
+		// The task which does not fit in block 1 is postponed past the full block 2.
+		run_to_block(&mut runtime, 1);
+		assert_eq!(runtime.scheduler.agenda(&2).len(), 3);
+		assert_eq!(runtime.scheduler.agenda(&3).len(), 1);
+	}
+
+	#[test]
+	fn postponed_periodic_tasks_keep_their_period() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+		for name in ["first", "second", "rent"] {
+			let call = transfer("bob", 10);
+			let origin = RawOrigin::Signed("alice");
+			scheduler::Pallet::schedule_named(&mut runtime, origin, name, 2, Some(3), call)
+				.unwrap();
+		}
+
+		// The third task is postponed to block 3, but still repeats from block 2.
+		run_to_block(&mut runtime, 2);
+		assert_eq!(runtime.scheduler.lookup(&"rent"), Some(&(3, 0)));
+		run_to_block(&mut runtime, 3);
+		assert_eq!(runtime.scheduler.lookup(&"rent"), Some(&(5, 2)));
+		assert_eq!(runtime.balances.balance(&"bob"), 30);
+	}
+}
@@ -0,0 +1,716 @@
+use std::collections::BTreeMap;
+
+use num::{CheckedAdd, CheckedSub, Zero};
+
+use crate::{
+	support::{self, Currency, DispatchResult, LockIdentifier, LockableCurrency, OnUnbalanced},
+	system::OriginFor,
+};
+
+/// The identifier of the lock the Staking Module puts on the bonded funds of an account.
+pub const STAKING_ID: LockIdentifier = *b"staking ";
+
+/// The index of an era, the period after which validators are rewarded.
+pub type EraIndex = u32;
+
+/// The type of the funds bonded by the stakers.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config: Sized + crate::system::Config + AsMut<Pallet<Self>> {
+	/// The currency the stakers bond, and are rewarded in.
+	type Currency: LockableCurrency<Self, Self::AccountId, Balance: Into<u128> + TryFrom<u128>>;
+	/// The handler of the funds slashed from stakers, like the treasury.
+	type Slash: OnUnbalanced<Self, BalanceOf<Self>>;
+	/// The handler of the part of the era reward which is not paid to any staker, like the
+	/// treasury.
+	type RewardRemainder: OnUnbalanced<Self, BalanceOf<Self>>;
+	/// The number of blocks in an era.
+	const BLOCKS_PER_ERA: Self::BlockNumber;
+	/// The number of eras unbonded funds stay locked for before they can be withdrawn.
+	const BONDING_DURATION: EraIndex;
+	/// The reward shared by the validators and their nominators at the end of every era.
+	const ERA_REWARD: BalanceOf<Self>;
+	/// The minimum amount an account must keep bonded.
+	const MIN_BOND: BalanceOf<Self>;
+	/// The maximum number of validators an account can nominate.
+	const MAX_NOMINATIONS: u32;
+}
+
+/// The events emitted by the Staking Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// `stash` bonded `amount` more.
+	Bonded { stash: T::AccountId, amount: BalanceOf<T> },
+	/// `stash` started unbonding `amount`.
+	Unbonded { stash: T::AccountId, amount: BalanceOf<T> },
+	/// `stash` withdrew `amount` of unbonded funds.
+	Withdrawn { stash: T::AccountId, amount: BalanceOf<T> },
+	/// `stash` declared itself a validator, keeping `commission` percent of its rewards.
+	ValidatorPrefsSet { stash: T::AccountId, commission: u8 },
+	/// `stash` nominated the `targets` validators.
+	Nominated { stash: T::AccountId, targets: Vec<T::AccountId> },
+	/// `stash` stopped validating or nominating.
+	Chilled { stash: T::AccountId },
+	/// The era `era` ended, with `payout` shared by the validators and their nominators.
+	EraPaid { era: EraIndex, payout: BalanceOf<T> },
+	/// `stash` was paid `amount` for the last era.
+	Rewarded { stash: T::AccountId, amount: BalanceOf<T> },
+	/// `amount` of the funds bonded by `stash` was slashed.
+	Slashed { stash: T::AccountId, amount: BalanceOf<T> },
+}
+
+/// A portion of bonded funds which is being unbonded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnlockChunk<Balance> {
+	/// The amount being unbonded.
+	pub value: Balance,
+	/// The era at which the funds can be withdrawn.
+	pub era: EraIndex,
+}
+
+/// The funds bonded by an account, which stay locked in its free balance.
+#[derive(Debug, Clone)]
+pub struct StakingLedger<T: Config> {
+	/// The funds which are staked, and earn rewards.
+	pub active: BalanceOf<T>,
+	/// The funds which are being unbonded, and earn no rewards.
+	pub unlocking: Vec<UnlockChunk<BalanceOf<T>>>,
+}
+
+impl<T: Config> StakingLedger<T> {
+	/// The funds which are bonded, active or being unbonded.
+	pub fn total(&self) -> BalanceOf<T> {
+		self.unlocking
+			.iter()
+			.fold(self.active, |total, chunk| total.checked_add(&chunk.value).unwrap_or(total))
+	}
+}
+
+/// The stake backing a validator during an era.
+#[derive(Debug, Clone)]
+pub struct Exposure<T: Config> {
+	/// The stake of the validator itself.
+	pub own: BalanceOf<T>,
+	/// The stake of each of the nominators of the validator.
+	pub others: Vec<(T::AccountId, BalanceOf<T>)>,
+	/// The commission of the validator, in percent.
+	pub commission: u8,
+}
+
+/// This is the Staking Module.
+/// It allows accounts to bond funds, and to use them to back themselves as validators, or to back
+/// other validators as nominators. The validators and their nominators are rewarded at the end of
+/// every era, in proportion to their stake.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The funds bonded by each account.
+	ledger: BTreeMap<T::AccountId, StakingLedger<T>>,
+	/// The commission of each account which wants to validate, in percent.
+	validators: BTreeMap<T::AccountId, u8>,
+	/// The validators nominated by each account.
+	nominators: BTreeMap<T::AccountId, Vec<T::AccountId>>,
+	/// The current era.
+	current_era: EraIndex,
+	/// The block at which the next era starts.
+	next_era_start: T::BlockNumber,
+	/// The stake backing each validator during the current era, which is fixed at its start.
+	exposures: BTreeMap<T::AccountId, Exposure<T>>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Staking Module.
+	pub fn new() -> Self {
+		Self {
+			ledger: BTreeMap::new(),
+			validators: BTreeMap::new(),
+			nominators: BTreeMap::new(),
+			current_era: 0,
+			next_era_start: T::BLOCKS_PER_ERA,
+			exposures: BTreeMap::new(),
+			events: Vec::new(),
+		}
+	}
+
+	/// Get the funds bonded by `stash`.
+	pub fn ledger(&self, stash: &T::AccountId) -> Option<&StakingLedger<T>> {
+		self.ledger.get(stash)
+	}
+
+	/// Get the commission of `stash`, if it wants to validate.
+	pub fn validator(&self, stash: &T::AccountId) -> Option<u8> {
+		self.validators.get(stash).copied()
+	}
+
+	/// Get the validators nominated by `stash`.
+	pub fn nominations(&self, stash: &T::AccountId) -> Option<&[T::AccountId]> {
+		self.nominators.get(stash).map(|targets| targets.as_slice())
+	}
+
+	/// Get the current era.
+	pub fn current_era(&self) -> EraIndex {
+		self.current_era
+	}
+
+	/// Get the stake backing `validator` during the current era.
+	pub fn exposure(&self, validator: &T::AccountId) -> Option<&Exposure<T>> {
+		self.exposures.get(validator)
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// Lock the funds bonded by `stash`, or remove the lock if it has none.
+	fn update_lock(runtime: &mut T, stash: &T::AccountId) {
+		let staking: &mut Self = runtime.as_mut();
+		let total = staking.ledger.get(stash).map(StakingLedger::total);
+		match total {
+			Some(total) => T::Currency::set_lock(runtime, STAKING_ID, stash, total),
+			None => T::Currency::remove_lock(runtime, STAKING_ID, stash),
+		}
+	}
+
+	/// Get the funds bonded by `stash`, failing if it has not bonded any.
+	fn bonded(&mut self, stash: &T::AccountId) -> Result<&mut StakingLedger<T>, &'static str> {
+		self.ledger.get_mut(stash).ok_or("Not bonded.")
+	}
+
+	/// Compute the stake backing each validator for a new era.
+	///
+	/// Each nominator backs the validators it nominated with an equal share of its active stake.
+	fn compute_exposures(&self) -> BTreeMap<T::AccountId, Exposure<T>> {
+		let mut exposures = self
+			.validators
+			.iter()
+			.filter_map(|(validator, commission)| {
+				let own = self.ledger.get(validator)?.active;
+				Some((
+					validator.clone(),
+					Exposure { own, others: Vec::new(), commission: *commission },
+				))
+			})
+			.collect::<BTreeMap<_, _>>();
+
+		for (nominator, targets) in &self.nominators {
+			let Some(ledger) = self.ledger.get(nominator) else { continue };
+			let targets = targets
+				.iter()
+				.filter(|target| exposures.contains_key(*target))
+				.cloned()
+				.collect::<Vec<_>>();
+			if targets.is_empty() {
+				continue;
+			}
+			let share = ledger.active.into() / targets.len() as u128;
+			let Ok(share) = BalanceOf::<T>::try_from(share) else { continue };
+			for target in targets {
+				let exposure = exposures.get_mut(&target).expect("Only validators are targeted.");
+				exposure.others.push((nominator.clone(), share));
+			}
+		}
+		exposures
+	}
+
+	/// Share the reward of the era which just ended among the validators and their nominators,
+	/// according to the exposures of the era. Each validator gets a part of the reward in
+	/// proportion to the total stake backing it. Returns the rewards of each account, which add up
+	/// to no more than `ERA_REWARD` because of rounding.
+	fn compute_rewards(&self) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		let exposures = self
+			.exposures
+			.iter()
+			.map(|(validator, exposure)| {
+				let total =
+					exposure.others.iter().fold(exposure.own.into(), |total: u128, (_, stake)| {
+						total.saturating_add((*stake).into())
+					});
+				(validator, exposure, total)
+			})
+			.collect::<Vec<_>>();
+		let total_stake = exposures
+			.iter()
+			.fold(0, |total_stake: u128, (_, _, total)| total_stake.saturating_add(*total));
+
+		let mut rewards = Vec::new();
+		for (validator, exposure, total) in exposures {
+			let reward = T::ERA_REWARD
+				.into()
+				.checked_mul(total)
+				.and_then(|reward| reward.checked_div(total_stake))
+				.unwrap_or_default();
+			let commission = reward.saturating_mul(u128::from(exposure.commission)) / 100;
+			let to_share = reward - commission;
+			let share_of = |stake: BalanceOf<T>| {
+				to_share.checked_mul(stake.into()).and_then(|share| share.checked_div(total))
+			};
+
+			let own_share = share_of(exposure.own).unwrap_or_default();
+			rewards.push((validator.clone(), commission.saturating_add(own_share)));
+			for (nominator, stake) in &exposure.others {
+				rewards.push((nominator.clone(), share_of(*stake).unwrap_or_default()));
+			}
+		}
+		rewards
+			.into_iter()
+			.filter_map(|(who, reward)| Some((who, BalanceOf::<T>::try_from(reward).ok()?)))
+			.filter(|(_, reward)| !reward.is_zero())
+			.collect()
+	}
+
+	/// End the current era, paying its rewards, and start a new one. If any validator was backed
+	/// during the era, the part of the reward which is not paid is given to `RewardRemainder`.
+	fn new_era(runtime: &mut T, now: T::BlockNumber) {
+		let staking: &mut Self = runtime.as_mut();
+		let rewards = staking.compute_rewards();
+		let era = staking.current_era;
+
+		let mut payout = BalanceOf::<T>::zero();
+		for (stash, reward) in rewards {
+			if T::Currency::deposit_creating(runtime, &stash, reward).is_err() {
+				continue;
+			}
+
+			let staking: &mut Self = runtime.as_mut();
+			staking.deposit_event(Event::Rewarded { stash, amount: reward });
+			payout = payout.checked_add(&reward).unwrap_or(payout);
+		}
+
+		let staking: &mut Self = runtime.as_mut();
+		staking.deposit_event(Event::EraPaid { era, payout });
+		if !staking.exposures.is_empty() {
+			let remainder = T::ERA_REWARD.checked_sub(&payout).unwrap_or_else(BalanceOf::<T>::zero);
+			if !remainder.is_zero() {
+				T::RewardRemainder::on_unbalanced(runtime, remainder);
+			}
+		}
+
+		let staking: &mut Self = runtime.as_mut();
+		staking.current_era += 1;
+		staking.next_era_start = now.checked_add(&T::BLOCKS_PER_ERA).unwrap_or(now);
+		staking.exposures = staking.compute_exposures();
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Bond `value` of the funds of the caller, which must not have bonded any yet. The funds stay
+	/// in the free balance of the caller, but are locked.
+	pub fn bond(runtime: &mut T, caller: T::AccountId, value: BalanceOf<T>) -> DispatchResult {
+		let staking: &mut Self = runtime.as_mut();
+		if staking.ledger.contains_key(&caller) {
+			return Err("Already bonded.");
+		}
+		if value < T::MIN_BOND {
+			return Err("Cannot bond less than the minimum bond.");
+		}
+
+		if T::Currency::free_balance(runtime, &caller) < value {
+			return Err("Not enough funds!");
+		}
+
+		let staking: &mut Self = runtime.as_mut();
+		staking
+			.ledger
+			.insert(caller.clone(), StakingLedger { active: value, unlocking: Vec::new() });
+		Self::update_lock(runtime, &caller);
+		let staking: &mut Self = runtime.as_mut();
+		staking.deposit_event(Event::Bonded { stash: caller, amount: value });
+		Ok(())
+	}
+
+	/// Bond `value` more of the funds of the caller, which must have bonded already.
+	pub fn bond_extra(
+		runtime: &mut T,
+		caller: T::AccountId,
+		value: BalanceOf<T>,
+	) -> DispatchResult {
+		let staking: &mut Self = runtime.as_mut();
+		let ledger = staking.bonded(&caller)?;
+		let active = ledger.active.checked_add(&value).ok_or("Overflow")?;
+		let total = ledger.total().checked_add(&value).ok_or("Overflow")?;
+
+		if T::Currency::free_balance(runtime, &caller) < total {
+			return Err("Not enough funds!");
+		}
+
+		let staking: &mut Self = runtime.as_mut();
+		staking.bonded(&caller)?.active = active;
+		Self::update_lock(runtime, &caller);
+		let staking: &mut Self = runtime.as_mut();
+		staking.deposit_event(Event::Bonded { stash: caller, amount: value });
+		Ok(())
+	}
+
+	/// Start unbonding `value` of the funds bonded by the caller. They stop earning rewards from
+	/// the next era, and can be withdrawn after `BONDING_DURATION` eras.
+	pub fn unbond(&mut self, caller: T::AccountId, value: BalanceOf<T>) -> DispatchResult {
+		let era = self.current_era.checked_add(T::BONDING_DURATION).ok_or("Overflow")?;
+		let ledger = self.bonded(&caller)?;
+		let active =
+			ledger.active.checked_sub(&value).ok_or("Cannot unbond more than is bonded.")?;
+		if !active.is_zero() && active < T::MIN_BOND {
+			return Err("Cannot keep less than the minimum bond.");
+		}
+
+		ledger.active = active;
+		ledger.unlocking.push(UnlockChunk { value, era });
+		self.deposit_event(Event::Unbonded { stash: caller, amount: value });
+		Ok(())
+	}
+
+	/// Unlock the funds of the caller which are done unbonding. Once all the funds of the caller
+	/// are withdrawn, it stops validating or nominating.
+	pub fn withdraw_unbonded(runtime: &mut T, caller: T::AccountId) -> DispatchResult {
+		let staking: &mut Self = runtime.as_mut();
+		let current_era = staking.current_era;
+		let ledger = staking.bonded(&caller)?;
+
+		let mut value = BalanceOf::<T>::zero();
+		for chunk in ledger.unlocking.iter().filter(|chunk| chunk.era <= current_era) {
+			value = value.checked_add(&chunk.value).ok_or("Overflow")?;
+		}
+		ledger.unlocking.retain(|chunk| chunk.era > current_era);
+		if ledger.active.is_zero() && ledger.unlocking.is_empty() {
+			staking.ledger.remove(&caller);
+			staking.validators.remove(&caller);
+			staking.nominators.remove(&caller);
+		}
+
+		Self::update_lock(runtime, &caller);
+		let staking: &mut Self = runtime.as_mut();
+		staking.deposit_event(Event::Withdrawn { stash: caller, amount: value });
+		Ok(())
+	}
+
+	/// Declare the caller wants to validate from the next era, keeping `commission` percent of its
+	/// rewards before the rest is shared with its nominators.
+	pub fn validate(&mut self, caller: T::AccountId, commission: u8) -> DispatchResult {
+		if commission > 100 {
+			return Err("The commission cannot be more than 100 percent.");
+		}
+		self.bonded(&caller)?;
+
+		self.nominators.remove(&caller);
+		self.validators.insert(caller.clone(), commission);
+		self.deposit_event(Event::ValidatorPrefsSet { stash: caller, commission });
+		Ok(())
+	}
+
+	/// Declare the caller wants to back the `targets` validators from the next era.
+	pub fn nominate(&mut self, caller: T::AccountId, targets: Vec<T::AccountId>) -> DispatchResult {
+		if targets.is_empty() {
+			return Err("Must nominate at least one validator.");
+		}
+		if targets.len() > T::MAX_NOMINATIONS as usize {
+			return Err("Too many nominations.");
+		}
+		if !targets.iter().all(|target| self.validators.contains_key(target)) {
+			return Err("Can only nominate validators.");
+		}
+		self.bonded(&caller)?;
+
+		self.validators.remove(&caller);
+		self.nominators.insert(caller.clone(), targets.clone());
+		self.deposit_event(Event::Nominated { stash: caller, targets });
+		Ok(())
+	}
+
+	/// Slash up to `amount` of the active funds bonded by `stash`, which are given to the `Slash`
+	/// handler. Funds which are being unbonded are not slashed. Only root can slash.
+	pub fn slash(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		stash: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		let staking: &mut Self = runtime.as_mut();
+		let ledger = staking.bonded(&stash)?;
+		let slashed = amount.min(ledger.active);
+		let active = ledger.active.checked_sub(&slashed).ok_or("Overflow")?;
+
+		if T::Currency::free_balance(runtime, &stash) < slashed {
+			return Err("Not enough funds!");
+		}
+		let slashed = T::Currency::slash(runtime, &stash, slashed);
+		let reaped = T::Currency::total_balance(runtime, &stash).is_zero();
+
+		let staking: &mut Self = runtime.as_mut();
+		if reaped {
+			// The slash reaped the stash, so it has nothing left to bond, and no lock to update.
+			staking.ledger.remove(&stash);
+			staking.validators.remove(&stash);
+			staking.nominators.remove(&stash);
+		} else {
+			staking.bonded(&stash).expect("The stash is bonded.").active = active;
+		}
+		staking.deposit_event(Event::Slashed { stash: stash.clone(), amount: slashed });
+		Self::update_lock(runtime, &stash);
+		T::Slash::on_unbalanced(runtime, slashed);
+		Ok(())
+	}
+
+	/// Declare the caller no longer wants to validate or nominate from the next era.
+	pub fn chill(&mut self, caller: T::AccountId) -> DispatchResult {
+		self.bonded(&caller)?;
+
+		self.validators.remove(&caller);
+		self.nominators.remove(&caller);
+		self.deposit_event(Event::Chilled { stash: caller });
+		Ok(())
+	}
+}
+
+impl<T: Config> support::Hooks<T, T::BlockNumber> for Pallet<T> {
+	/// Start a new era every `BLOCKS_PER_ERA` blocks, paying the rewards of the last one.
+	fn on_initialize(runtime: &mut T, now: T::BlockNumber) {
+		let staking: &mut Self = runtime.as_mut();
+		if now >= staking.next_era_start {
+			Self::new_era(runtime, now);
+		}
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Event;
+	use crate::{
+		balances, staking,
+		support::{Dispatch, RawOrigin},
+		testing::account,
+	};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[hooks]
+		#[event]
+		staking: staking::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = String;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl staking::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		type Slash = ();
+		type RewardRemainder = ();
+		const BLOCKS_PER_ERA: u32 = 3;
+		const BONDING_DURATION: staking::EraIndex = 2;
+		const ERA_REWARD: u32 = 100;
+		const MIN_BOND: u32 = 10;
+		const MAX_NOMINATIONS: u32 = 2;
+	}
+
+	crate::testing::impl_run_to_block!();
+
+	crate::testing::impl_dispatch!(staking);
+
+	#[test]
+	fn bond_and_unbond() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+
+		let bond = |value| staking::Call::bond { value };
+		assert_eq!(
+			dispatch(&mut runtime, "alice", bond(5)),
+			Err("Cannot bond less than the minimum bond.")
+		);
+		assert_eq!(dispatch(&mut runtime, "alice", bond(50)), Ok(()));
+		assert_eq!(dispatch(&mut runtime, "alice", bond(10)), Err("Already bonded."));
+		assert_eq!(
+			dispatch(&mut runtime, "alice", staking::Call::bond_extra { value: 10 }),
+			Ok(())
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "alice", staking::Call::bond_extra { value: 50 }),
+			Err("Not enough funds!")
+		);
+		// The bonded funds stay with alice, but are locked.
+		assert_eq!(runtime.balances.balance(&account("alice")), 100);
+		assert_eq!(runtime.balances.locked(&account("alice")), 60);
+		assert_eq!(runtime.staking.ledger(&account("alice")).unwrap().active, 60);
+
+		let unbond = |value| staking::Call::unbond { value };
+		assert_eq!(
+			dispatch(&mut runtime, "alice", unbond(55)),
+			Err("Cannot keep less than the minimum bond.")
+		);
+		assert_eq!(dispatch(&mut runtime, "alice", unbond(20)), Ok(()));
+		assert_eq!(runtime.staking.ledger(&account("alice")).unwrap().active, 40);
+
+		// Unbonded funds cannot be withdrawn before the bonding duration has passed.
+		run_to_block(&mut runtime, 5);
+		assert_eq!(runtime.staking.current_era(), 1);
+		dispatch(&mut runtime, "alice", staking::Call::withdraw_unbonded {}).unwrap();
+		assert_eq!(runtime.balances.locked(&account("alice")), 60);
+
+		run_to_block(&mut runtime, 6);
+		assert_eq!(runtime.staking.current_era(), 2);
+		dispatch(&mut runtime, "alice", staking::Call::withdraw_unbonded {}).unwrap();
+		assert_eq!(runtime.balances.locked(&account("alice")), 40);
+		assert_eq!(runtime.staking.ledger(&account("alice")).unwrap().unlocking, vec![]);
+
+		// Withdrawing everything removes the ledger.
+		dispatch(&mut runtime, "alice", unbond(40)).unwrap();
+		run_to_block(&mut runtime, 12);
+		dispatch(&mut runtime, "alice", staking::Call::withdraw_unbonded {}).unwrap();
+		assert_eq!(runtime.balances.locked(&account("alice")), 0);
+		assert!(runtime.staking.ledger(&account("alice")).is_none());
+	}
+
+	#[test]
+	fn validate_and_nominate() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+		runtime.balances.set_balance(account("bob"), 100).unwrap();
+
+		let validate = |commission| staking::Call::validate { commission };
+		let nominate = |targets: &[&str]| staking::Call::nominate {
+			targets: targets.iter().map(|target| account(target)).collect(),
+		};
+		assert_eq!(dispatch(&mut runtime, "alice", validate(10)), Err("Not bonded."));
+		dispatch(&mut runtime, "alice", staking::Call::bond { value: 60 }).unwrap();
+		dispatch(&mut runtime, "bob", staking::Call::bond { value: 40 }).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, "alice", validate(101)),
+			Err("The commission cannot be more than 100 percent.")
+		);
+		assert_eq!(dispatch(&mut runtime, "alice", validate(10)), Ok(()));
+		assert_eq!(runtime.staking.validator(&account("alice")), Some(10));
+
+		assert_eq!(
+			dispatch(&mut runtime, "bob", nominate(&["charlie"])),
+			Err("Can only nominate validators.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, "bob", nominate(&["alice", "alice", "alice"])),
+			Err("Too many nominations.")
+		);
+		assert_eq!(dispatch(&mut runtime, "bob", nominate(&["alice"])), Ok(()));
+		assert_eq!(runtime.staking.nominations(&account("bob")), Some(&[account("alice")][..]));
+
+		// A nominator which starts validating is no longer a nominator.
+		dispatch(&mut runtime, "bob", validate(0)).unwrap();
+		assert_eq!(runtime.staking.nominations(&account("bob")), None);
+		dispatch(&mut runtime, "bob", staking::Call::chill {}).unwrap();
+		assert_eq!(runtime.staking.validator(&account("bob")), None);
+	}
+
+	#[test]
+	fn era_rewards() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+		runtime.balances.set_balance(account("bob"), 100).unwrap();
+		dispatch(&mut runtime, "alice", staking::Call::bond { value: 60 }).unwrap();
+		dispatch(&mut runtime, "alice", staking::Call::validate { commission: 10 }).unwrap();
+		dispatch(&mut runtime, "bob", staking::Call::bond { value: 40 }).unwrap();
+		let nominate = staking::Call::nominate { targets: vec![account("alice")] };
+		dispatch(&mut runtime, "bob", nominate).unwrap();
+
+		// No one was staking during the first era, so there are no rewards for it.
+		run_to_block(&mut runtime, 3);
+		assert!(matches!(
+			runtime.system.events(),
+			[RuntimeEvent::staking(Event::EraPaid { era: 0, payout: 0 })]
+		));
+		let exposure = runtime.staking.exposure(&account("alice")).unwrap();
+		assert_eq!(exposure.own, 60);
+		assert_eq!(exposure.others, vec![(account("bob"), 40)]);
+
+		// Alice keeps her commission of the reward, and shares the rest with bob in proportion to
+		// their stake.
+		run_to_block(&mut runtime, 6);
+		assert_eq!(runtime.balances.balance(&account("alice")), 100 + 10 + 54);
+		assert_eq!(runtime.balances.balance(&account("bob")), 100 + 36);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::staking(Event::EraPaid { era: 1, payout: 100 }))
+		));
+	}
+
+	#[test]
+	fn rewards_follow_the_stake_of_each_validator() {
+		let mut runtime = Runtime::new();
+		for (who, value) in [("alice", 60), ("charlie", 30)] {
+			runtime.balances.set_balance(account(who), 100).unwrap();
+			dispatch(&mut runtime, who, staking::Call::bond { value }).unwrap();
+			dispatch(&mut runtime, who, staking::Call::validate { commission: 0 }).unwrap();
+		}
+
+		// Alice is backed by twice the stake of charlie, so she gets twice the reward. What is
+		// left after rounding goes to `RewardRemainder` rather than to the first validators.
+		run_to_block(&mut runtime, 6);
+		assert_eq!(runtime.balances.balance(&account("alice")), 100 + 66);
+		assert_eq!(runtime.balances.balance(&account("charlie")), 100 + 33);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::staking(Event::EraPaid { era: 1, payout: 99 }))
+		));
+	}
+
+	#[test]
+	fn slash() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+		dispatch(&mut runtime, "alice", staking::Call::bond { value: 60 }).unwrap();
+		dispatch(&mut runtime, "alice", staking::Call::unbond { value: 20 }).unwrap();
+
+		let slash = |stash: &str, amount| {
+			RuntimeCall::staking(staking::Call::slash { stash: account(stash), amount })
+		};
+		assert_eq!(
+			runtime.dispatch(RawOrigin::Signed(account("bob")), slash("alice", 10)),
+			Err("Bad origin: expected the root origin.")
+		);
+
+		// Only the active funds are slashed.
+		runtime.dispatch(RawOrigin::Root, slash("alice", 50)).unwrap();
+		assert_eq!(runtime.staking.ledger(&account("alice")).unwrap().active, 0);
+		assert_eq!(runtime.balances.balance(&account("alice")), 60);
+		assert_eq!(runtime.balances.locked(&account("alice")), 20);
+
+		// A slash which fails leaves the ledger as it was, without an event.
+		dispatch(&mut runtime, "alice", staking::Call::bond_extra { value: 30 }).unwrap();
+		runtime.balances.set_balance(account("alice"), 10).unwrap();
+		let events = runtime.system.events().len();
+		assert_eq!(runtime.dispatch(RawOrigin::Root, slash("alice", 30)), Err("Not enough funds!"));
+		assert_eq!(runtime.staking.ledger(&account("alice")).unwrap().active, 30);
+		assert_eq!(runtime.system.events().len(), events);
+
+		// A slash which reaps the stash removes its ledger, rather than locking funds it no longer
+		// has.
+		runtime.balances.set_balance(account("bob"), 50).unwrap();
+		dispatch(&mut runtime, "bob", staking::Call::bond { value: 50 }).unwrap();
+		dispatch(&mut runtime, "bob", staking::Call::validate { commission: 0 }).unwrap();
+		runtime.dispatch(RawOrigin::Root, slash("bob", 50)).unwrap();
+		assert_eq!(runtime.balances.total_balance(&account("bob")), 0);
+		assert!(runtime.staking.ledger(&account("bob")).is_none());
+		assert_eq!(runtime.staking.validator(&account("bob")), None);
+		assert_eq!(runtime.balances.locked(&account("bob")), 0);
+	}
+}
@@ -1,3 +1,12 @@
+use core::fmt::Debug;
+use num::{CheckedAdd, CheckedSub, Zero};
+use sha2::{Digest, Sha256};
+use std::{
+	any::Any,
+	collections::BTreeMap,
+	hash::{Hash, Hasher},
+};
+
 /// The most primitive representation of a Blockchain block.
 pub struct Block<Header, Extrinsic> {
 	/// The block header contains metadata about the block.
@@ -19,11 +28,87 @@ pub struct Header<BlockNumber> {
 /// This is an "extrinsic": literally an external message from outside of the blockchain.
 /// This simplified version of an extrinsic tells us who is making the call, and which call they are
 /// making.
+///
+/// Extrinsics without a `caller` are "inherents": unsigned calls inserted by the block author, such
+/// as the block timestamp. They are never charged to anyone, and must all appear exactly once at
+/// the start of the block.
 pub struct Extrinsic<Caller, Call> {
-	pub caller: Caller,
+	pub caller: Option<Caller>,
 	pub call: Call,
 }
 
+impl<Caller, Call> Extrinsic<Caller, Call> {
+	/// Create a new extrinsic signed by `caller`.
+	pub fn new_signed(caller: Caller, call: Call) -> Self {
+		Self { caller: Some(caller), call }
+	}
+
+	/// Create a new unsigned inherent extrinsic.
+	pub fn new_inherent(call: Call) -> Self {
+		Self { caller: None, call }
+	}
+
+	/// Whether this extrinsic was signed by some caller.
+	pub fn is_signed(&self) -> bool {
+		self.caller.is_some()
+	}
+}
+
+/// The call type of a runtime `T`, which is the accumulation of the calls of all its pallets.
+pub type CallOf<T> = <T as Dispatch>::Call;
+
+/// Execute `f` on `state` as a transaction: if it returns an error, all the changes it made to
+/// `state` are reverted.
+///
+/// This simply takes a snapshot of the state before executing `f`, which is fine for the small
+/// in-memory state of this runtime.
+pub fn with_transaction<S: Clone, R>(
+	state: &mut S,
+	f: impl FnOnce(&mut S) -> Result<R, &'static str>,
+) -> Result<R, &'static str> {
+	let snapshot = state.clone();
+	let result = f(state);
+	if result.is_err() {
+		*state = snapshot;
+	}
+	result
+}
+
+/// The origin of a call, which tells us on whose behalf the call is being executed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RawOrigin<AccountId> {
+	/// The call is executed with the highest privilege, by the runtime itself.
+	Root,
+	/// The call is executed on behalf of an account which signed the extrinsic.
+	Signed(AccountId),
+	/// The call is not executed on behalf of anyone, as for inherents.
+	None,
+}
+
+/// Ensure that the `origin` is a signed account, and return that account.
+pub fn ensure_signed<AccountId>(origin: RawOrigin<AccountId>) -> Result<AccountId, &'static str> {
+	match origin {
+		RawOrigin::Signed(who) => Ok(who),
+		_ => Err("Bad origin: expected a signed origin."),
+	}
+}
+
+/// Ensure that the `origin` is root.
+pub fn ensure_root<AccountId>(origin: RawOrigin<AccountId>) -> DispatchResult {
+	match origin {
+		RawOrigin::Root => Ok(()),
+		_ => Err("Bad origin: expected the root origin."),
+	}
+}
+
+/// Ensure that the `origin` is none, i.e. the call comes from an inherent.
+pub fn ensure_none<AccountId>(origin: RawOrigin<AccountId>) -> DispatchResult {
+	match origin {
+		RawOrigin::None => Ok(()),
+		_ => Err("Bad origin: expected no origin."),
+	}
+}
+
 /// The Result type for our runtime. When everything completes successfully, we return `Ok(())`,
 /// otherwise return a static error message.
 pub type DispatchResult = Result<(), &'static str>;
@@ -40,3 +125,311 @@ pub trait Dispatch {
 	/// based on the outcome of that function call.
 	fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> DispatchResult;
 }
+
+/// A trait for calls which know the names of the pallet and function they are routed to.
+/// This is implemented for `RuntimeCall` by `#[macros::runtime]`.
+pub trait GetCallMetadata {
+	/// The name of the pallet the call is routed to, as named in the `Runtime` struct.
+	fn pallet_name(&self) -> &'static str;
+	/// The name of the function the call is routed to.
+	fn call_name(&self) -> &'static str;
+}
+
+/// A trait for permission scopes, like the types of a proxy, which only allow some calls.
+pub trait InstanceFilter<Call> {
+	/// Whether this scope allows `call` to be dispatched.
+	fn filter(&self, call: &Call) -> bool;
+}
+
+/// A handler for funds which were taken out of an account without being paid to another, like
+/// slashes. The funds are burned by default, unless the handler credits them to some account, like
+/// the treasury's.
+pub trait OnUnbalanced<Runtime, Balance> {
+	/// Handle `amount` of funds taken out of some account.
+	fn on_unbalanced(_runtime: &mut Runtime, _amount: Balance) {}
+}
+
+impl<Runtime, Balance> OnUnbalanced<Runtime, Balance> for () {}
+
+/// A source of the current time, like the timestamp pallet. Like `OnUnbalanced`, it is given the
+/// whole `runtime`. The time is not available by default.
+pub trait Time<Runtime> {
+	/// The type used to represent a point in time.
+	type Moment: Copy + Debug;
+
+	/// The current time, if it is available.
+	fn now(_runtime: &Runtime) -> Option<Self::Moment> {
+		None
+	}
+}
+
+impl<Runtime> Time<Runtime> for () {
+	type Moment = ();
+}
+
+/// A currency, whose funds are held in the accounts of `AccountId`. Pallets which need funds take
+/// some `Currency` in their `Config`, rather than depending on the balances pallet directly.
+///
+/// Like `OnUnbalanced`, the functions of a currency are given the whole `runtime`, so that they can
+/// reach the pallet implementing it.
+pub trait Currency<Runtime, AccountId> {
+	/// The type of the funds of an account.
+	type Balance: Zero + CheckedSub + CheckedAdd + Copy + Ord + Debug;
+
+	/// The total amount of funds in existence.
+	fn total_issuance(runtime: &Runtime) -> Self::Balance;
+	/// The minimum balance an account must have to exist.
+	fn minimum_balance() -> Self::Balance;
+	/// The total balance of `who`, free and reserved.
+	fn total_balance(runtime: &Runtime, who: &AccountId) -> Self::Balance;
+	/// The free balance of `who`, which can be transferred unless it is locked.
+	fn free_balance(runtime: &Runtime, who: &AccountId) -> Self::Balance;
+	/// Transfer `amount` of the free balance of `from` to `to`, which must not be locked. Unlike
+	/// the calls of a pallet, this emits no event.
+	fn transfer(
+		runtime: &mut Runtime,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+	/// Remove `amount` from the free balance of `who`, which must not be locked. The funds are
+	/// burned, unless the caller deposits them somewhere else, or hands them to `OnUnbalanced`.
+	fn withdraw(runtime: &mut Runtime, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+	/// Remove up to `amount` of the free balance of `who`, even if it is locked. Returns the
+	/// amount actually slashed, which the caller should hand to `OnUnbalanced`.
+	fn slash(runtime: &mut Runtime, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+	/// Add `amount` to the free balance of `who`, minting it. The account must end up with at
+	/// least the minimum balance.
+	fn deposit_creating(
+		runtime: &mut Runtime,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+}
+
+/// Where funds moved out of a reserved balance end up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceStatus {
+	/// The funds are added to the free balance of the beneficiary.
+	Free,
+	/// The funds are added to the reserved balance of the beneficiary.
+	Reserved,
+}
+
+/// A currency whose funds can be reserved, like deposits: reserved funds stay in the account, but
+/// cannot be transferred until they are unreserved.
+pub trait ReservableCurrency<Runtime, AccountId>: Currency<Runtime, AccountId> {
+	/// The reserved balance of `who`.
+	fn reserved_balance(runtime: &Runtime, who: &AccountId) -> Self::Balance;
+	/// Move `amount` of the free balance of `who` to its reserved balance.
+	fn reserve(runtime: &mut Runtime, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+	/// Move up to `amount` of the reserved balance of `who` back to its free balance. Returns the
+	/// amount actually unreserved.
+	fn unreserve(runtime: &mut Runtime, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+	/// Remove up to `amount` of the reserved balance of `who`. Returns the amount actually
+	/// slashed, which the caller should hand to `OnUnbalanced`.
+	fn slash_reserved(
+		runtime: &mut Runtime,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> Self::Balance;
+	/// Move up to `amount` of the reserved balance of `slashed` to `beneficiary`, depending on
+	/// `status`. Returns the amount actually moved.
+	fn repatriate_reserved(
+		runtime: &mut Runtime,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		amount: Self::Balance,
+		status: BalanceStatus,
+	) -> Result<Self::Balance, &'static str>;
+}
+
+/// The identifier of a lock, so that each pallet can manage its own lock on an account.
+pub type LockIdentifier = [u8; 8];
+
+/// A currency whose funds can be locked: locked funds stay in the free balance, but cannot be
+/// transferred or reserved. Locks overlap, rather than add up.
+pub trait LockableCurrency<Runtime, AccountId>: Currency<Runtime, AccountId> {
+	/// Lock `amount` of the free balance of `who` under `id`, replacing any previous lock with the
+	/// same identifier.
+	fn set_lock(runtime: &mut Runtime, id: LockIdentifier, who: &AccountId, amount: Self::Balance);
+	/// Remove the lock with the identifier `id` on the balance of `who`.
+	fn remove_lock(runtime: &mut Runtime, id: LockIdentifier, who: &AccountId);
+}
+
+/// The weight of some computation, used to limit how much work is done in a single block.
+pub type Weight = u64;
+
+/// Hooks which pallets can implement to run some logic at the start and at the end of every block.
+/// Like calls taking `runtime: &mut T`, they are given the whole `runtime` including the pallet.
+///
+/// Pallets implementing this trait must be marked with `#[hooks]` in the `Runtime` struct.
+pub trait Hooks<Runtime, BlockNumber> {
+	/// Run at the start of block `n`, after the inherents and before any signed extrinsic.
+	fn on_initialize(_runtime: &mut Runtime, _n: BlockNumber) {}
+
+	/// Run at the end of block `n`, after all extrinsics are executed.
+	fn on_finalize(_runtime: &mut Runtime, _n: BlockNumber) {}
+}
+
+/// A trait for pallets which emit events.
+///
+/// Events are first kept by the pallet itself, so that calls which only have access to their own
+/// pallet can emit them too. The runtime then collects them into the system pallet after every
+/// call and hook.
+///
+/// Pallets implementing this trait must be marked with `#[event]` in the `Runtime` struct.
+pub trait PendingEvents {
+	/// The event type of the pallet.
+	type Event;
+
+	/// Take all the events emitted by the pallet since they were last collected.
+	fn take_events(&mut self) -> Vec<Self::Event>;
+}
+
+/// The identifier of a piece of inherent data.
+pub type InherentIdentifier = [u8; 8];
+
+/// The data provided by the block author to create the inherents of a new block, like the current
+/// time. Each pallet providing an inherent looks up its own data using an `InherentIdentifier`.
+#[derive(Default)]
+pub struct InherentData {
+	data: BTreeMap<InherentIdentifier, Box<dyn Any>>,
+}
+
+impl InherentData {
+	/// Create a new, empty set of inherent data.
+	pub fn new() -> Self {
+		Self { data: BTreeMap::new() }
+	}
+
+	/// Put some `data` under the `identifier`, replacing any previous data.
+	pub fn put_data<T: Any>(&mut self, identifier: InherentIdentifier, data: T) {
+		self.data.insert(identifier, Box::new(data));
+	}
+
+	/// Get the data under the `identifier`, if it exists and has the expected type.
+	pub fn get_data<T: Any + Clone>(&self, identifier: &InherentIdentifier) -> Option<T> {
+		self.data.get(identifier).and_then(|data| data.downcast_ref::<T>()).cloned()
+	}
+}
+
+/// A trait for pallets which provide an inherent: an unsigned call which the block author must
+/// include exactly once, at the start of every block.
+///
+/// Pallets implementing this trait must be marked with `#[inherent]` in the `Runtime` struct.
+pub trait ProvideInherent {
+	/// The call type of the pallet.
+	type Call;
+
+	/// Create the inherent call for a new block from the `data` provided by the block author.
+	/// Returns `None` if the data needed is missing.
+	fn create_inherent(&self, data: &InherentData) -> Option<Self::Call>;
+
+	/// Whether `call` is the inherent call of this pallet.
+	fn is_inherent(call: &Self::Call) -> bool;
+}
+
+/// A 256 bit hash, like the hash of a call.
+pub type H256 = [u8; 32];
+
+/// A `Hasher` which feeds everything it is given into a SHA-256 digest.
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+	fn write(&mut self, bytes: &[u8]) {
+		self.0.update(bytes);
+	}
+
+	// Only used by `HashMap` and the like, which need a `u64`. Use `hash_of` for the full hash.
+	fn finish(&self) -> u64 {
+		let hash = self.0.clone().finalize();
+		u64::from_le_bytes(hash[..8].try_into().expect("The hash is longer than 8 bytes."))
+	}
+}
+
+/// Hash some `data` with SHA-256.
+pub fn sha2_256(data: &[u8]) -> H256 {
+	Sha256::digest(data).into()
+}
+
+/// Hash any `value` implementing `Hash` with SHA-256, like a call or a list of accounts.
+///
+/// We have no encoding for the types of this runtime, so we rely on the bytes they feed to their
+/// `Hash` implementation instead. These are stable for a given build, which is all this in-memory
+/// runtime needs.
+pub fn hash_of<T: Hash + ?Sized>(value: &T) -> H256 {
+	let mut hasher = Sha256Hasher(Sha256::new());
+	value.hash(&mut hasher);
+	hasher.0.finalize().into()
+}
+
+/// A trait for account ids which can be derived deterministically from a hash, for accounts which
+/// no one holds the key of, like the account of a multisig or of a pallet.
+pub trait DeriveAccountId {
+	/// Derive the account id from the `hash`.
+	fn derive_account_id(hash: &H256) -> Self;
+}
+
+impl DeriveAccountId for String {
+	fn derive_account_id(hash: &H256) -> Self {
+		let hex = hash.iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+		format!("0x{}", hex)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn inherent_data() {
+		let mut data = InherentData::new();
+		assert_eq!(data.get_data::<u64>(b"testinh0"), None);
+		data.put_data(*b"testinh0", 42u64);
+		assert_eq!(data.get_data::<u64>(b"testinh0"), Some(42));
+		// The data is only returned when asked for with the right type.
+		assert_eq!(data.get_data::<u32>(b"testinh0"), None);
+		assert_eq!(data.get_data::<u64>(b"testinh1"), None);
+	}
+
+	#[test]
+	fn transactions() {
+		let mut state = vec![1, 2];
+		let result = with_transaction(&mut state, |state| {
+			state.push(3);
+			Ok(state.len())
+		});
+		assert_eq!(result, Ok(3));
+		assert_eq!(state, vec![1, 2, 3]);
+
+		// All the changes are reverted when the transaction fails.
+		let result: Result<(), _> = with_transaction(&mut state, |state| {
+			state.clear();
+			Err("failed")
+		});
+		assert_eq!(result, Err("failed"));
+		assert_eq!(state, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn hashing() {
+		assert_eq!(sha2_256(b"abc")[..4], [0xba, 0x78, 0x16, 0xbf],);
+		assert_eq!(hash_of(&("alice", 2u16)), hash_of(&("alice", 2u16)));
+		assert_ne!(hash_of(&("alice", 2u16)), hash_of(&("alice", 3u16)));
+
+		let account = String::derive_account_id(&sha2_256(b"abc"));
+		assert!(account.starts_with("0xba7816bf"));
+		assert_eq!(account.len(), 66);
+	}
+
+	#[test]
+	fn origins() {
+		assert_eq!(ensure_signed(RawOrigin::Signed("alice")), Ok("alice"));
+		assert!(ensure_signed::<&str>(RawOrigin::Root).is_err());
+		assert_eq!(ensure_root::<&str>(RawOrigin::Root), Ok(()));
+		assert!(ensure_root(RawOrigin::Signed("alice")).is_err());
+		assert_eq!(ensure_none::<&str>(RawOrigin::None), Ok(()));
+		assert!(ensure_none(RawOrigin::Signed("alice")).is_err());
+	}
+}
@@ -1,27 +1,43 @@
+use core::fmt::Debug;
 use std::{collections::BTreeMap, ops::AddAssign};
 
-use num::{One, Zero};
+use num::{CheckedAdd, One, Zero};
+
+/// The origin of a call made to a pallet configured with `T`.
+pub type OriginFor<T> = crate::support::RawOrigin<<T as Config>::AccountId>;
 
 pub trait Config {
-	type BlockNumber: Zero + One + AddAssign + Copy;
-	type AccountId: Ord;
+	type BlockNumber: Zero + One + AddAssign + CheckedAdd + Copy + Ord + Debug;
+	type AccountId: Ord + Clone + Debug;
 	type Nonce: Zero + One + Copy;
+	/// The aggregated event type of the runtime, which the events of all pallets are collected
+	/// into.
+	type RuntimeEvent;
 }
 
 /// This is the System Pallet.
 /// It handles low level state needed for your blockchain.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pallet<T: Config> {
 	/// The current block number.
 	pub block_number: T::BlockNumber,
 	/// A map from an account to their nonce.
 	pub nonce: BTreeMap<T::AccountId, T::Nonce>,
+	/// The index in the current block of the extrinsic being executed, if any.
+	extrinsic_index: Option<u32>,
+	/// The events emitted during the current block.
+	events: Vec<T::RuntimeEvent>,
 }
 
 impl<T: Config> Pallet<T> {
 	/// Create a new instance of the System Pallet.
 	pub fn new() -> Self {
-		Self { block_number: T::BlockNumber::zero(), nonce: BTreeMap::new() }
+		Self {
+			block_number: T::BlockNumber::zero(),
+			nonce: BTreeMap::new(),
+			extrinsic_index: None,
+			events: Vec::new(),
+		}
 	}
 
 	/// Get the current block number.
@@ -34,6 +50,18 @@ impl<T: Config> Pallet<T> {
 		self.block_number += T::BlockNumber::one();
 	}
 
+	/// Get the index in the current block of the extrinsic being executed, or `None` outside of
+	/// an extrinsic, like in the hooks of the pallets.
+	pub fn extrinsic_index(&self) -> Option<u32> {
+		self.extrinsic_index
+	}
+
+	/// Note that the extrinsic at `index` in the current block is being executed, or that none is
+	/// for `None`.
+	pub fn set_extrinsic_index(&mut self, index: Option<u32>) {
+		self.extrinsic_index = index;
+	}
+
 	/// Increment the nonce of an account. This helps us keep track of how many transactions each
 	/// account has made.
 	pub fn inc_nonce(&mut self, who: T::AccountId) {
@@ -42,6 +70,27 @@ impl<T: Config> Pallet<T> {
 			.and_modify(|curr| *curr = *curr + T::Nonce::one())
 			.or_insert(T::Nonce::one());
 	}
+
+	/// Remove the state of an account which no longer exists, so that it does not grow without
+	/// bound.
+	pub fn kill_account(&mut self, who: &T::AccountId) {
+		self.nonce.remove(who);
+	}
+
+	/// Get the events emitted during the current block.
+	pub fn events(&self) -> &[T::RuntimeEvent] {
+		&self.events
+	}
+
+	/// Deposit an `event` emitted during the current block.
+	pub fn deposit_event(&mut self, event: T::RuntimeEvent) {
+		self.events.push(event);
+	}
+
+	/// Remove all the events, which is done at the start of every block.
+	pub fn reset_events(&mut self) {
+		self.events.clear();
+	}
 }
 
 #[cfg(test)]
@@ -55,6 +104,8 @@ mod tests {
 		type AccountId = String;
 
 		type Nonce = u32;
+
+		type RuntimeEvent = ();
 	}
 
 	#[test]
@@ -68,5 +119,9 @@ mod tests {
 		assert_eq!(pallet.block_number, 1);
 		pallet.inc_nonce("Wassim".to_string());
 		assert_eq!(pallet.nonce.get("Wassim").unwrap(), &1);
+		pallet.deposit_event(());
+		assert_eq!(pallet.events().len(), 1);
+		pallet.reset_events();
+		assert!(pallet.events().is_empty());
 	}
 }
@@ -0,0 +1,55 @@
+//! Helpers shared by the tests of the pallets.
+//!
+//! Each test module defines its own `Runtime` with the `macros::runtime` attribute, so the helpers
+//! which need it are macros, expanded in the test module next to its `Runtime`.
+
+/// The account of `name`, for test runtimes using `String` accounts.
+pub fn account(name: &str) -> String {
+	name.to_string()
+}
+
+/// Define `fn run_to_block(runtime, n)`, which executes empty blocks until the block number of the
+/// `Runtime` reaches `n`. The inherent data of each block is given by `$inherent_data`, a function
+/// of the runtime before the block, or is empty.
+macro_rules! impl_run_to_block {
+	() => {
+		crate::testing::impl_run_to_block!(|_: &Runtime| crate::support::InherentData::new());
+	};
+	($inherent_data:expr) => {
+		fn run_to_block(runtime: &mut Runtime, n: u32) {
+			while runtime.system.block_number() < n {
+				let data = ($inherent_data)(&*runtime);
+				let block = runtime.build_block(&data, vec![]);
+				runtime.execute_block(block).unwrap();
+			}
+		}
+	};
+}
+
+/// Define `fn dispatch(runtime, caller, call)`, which dispatches a `call` of `$pallet` on the
+/// `Runtime`, signed by `caller`. With `origin`, the function takes the whole origin of the call
+/// instead of the caller.
+macro_rules! impl_dispatch {
+	($pallet:ident) => {
+		fn dispatch(
+			runtime: &mut Runtime,
+			caller: &'static str,
+			call: $pallet::Call<Runtime>,
+		) -> crate::support::DispatchResult {
+			let origin = crate::support::RawOrigin::Signed(caller.into());
+			crate::support::Dispatch::dispatch(runtime, origin, RuntimeCall::$pallet(call))
+		}
+	};
+	($pallet:ident, origin) => {
+		fn dispatch(
+			runtime: &mut Runtime,
+			origin: crate::support::RawOrigin<<Runtime as crate::system::Config>::AccountId>,
+			call: $pallet::Call<Runtime>,
+		) -> crate::support::DispatchResult {
+			crate::support::Dispatch::dispatch(runtime, origin, RuntimeCall::$pallet(call))
+		}
+	};
+}
+
+pub(crate) use impl_dispatch;
+pub(crate) use impl_run_to_block;
@@ -0,0 +1,257 @@
+use core::fmt::Debug;
+
+use num::{CheckedAdd, Zero};
+
+use crate::{
+	support::{self, DispatchResult, InherentData, InherentIdentifier},
+	system::OriginFor,
+};
+
+/// The identifier of the current time in the `InherentData` provided by the block author.
+pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"timstap0";
+
+pub trait Config: crate::system::Config {
+	/// The type used to represent a point in time, like milliseconds since the unix epoch.
+	type Moment: Zero + CheckedAdd + Copy + Ord + Debug + 'static;
+	/// The minimum amount of time between the timestamps of two blocks.
+	const MINIMUM_PERIOD: Self::Moment;
+	/// The maximum amount of time the timestamp of a block can be ahead of the previous one.
+	const MAXIMUM_DRIFT: Self::Moment;
+}
+
+/// This is the Timestamp Module.
+/// It keeps track of the current time, which the block author sets once per block through an
+/// inherent.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The timestamp of the current block, if it was ever set.
+	now: Option<T::Moment>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Timestamp Module.
+	pub fn new() -> Self {
+		Self { now: None }
+	}
+
+	/// Get the timestamp of the current block, if it was ever set.
+	pub fn now(&self) -> Option<T::Moment> {
+		self.now
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Set the timestamp of the current block to `now`.
+	/// This is an inherent, so it can only be called with no origin. The new timestamp must be at
+	/// least `MINIMUM_PERIOD` and at most `MAXIMUM_DRIFT` after the previous one.
+	pub fn set(&mut self, origin: OriginFor<T>, now: T::Moment) -> DispatchResult {
+		support::ensure_none(origin)?;
+
+		// There is no previous timestamp to compare with for the first block.
+		if let Some(previous) = self.now {
+			let earliest = previous.checked_add(&T::MINIMUM_PERIOD).ok_or("Overflow")?;
+			let latest = previous.checked_add(&T::MAXIMUM_DRIFT).ok_or("Overflow")?;
+			if now < earliest {
+				return Err("Timestamp must increment by at least the minimum period.");
+			}
+			if now > latest {
+				return Err("Timestamp is too far ahead of the previous one.");
+			}
+		}
+
+		self.now = Some(now);
+		Ok(())
+	}
+}
+
+/// The timestamp of the current block, once it was set.
+impl<T: Config + AsRef<Pallet<T>>> support::Time<T> for Pallet<T> {
+	type Moment = T::Moment;
+
+	fn now(runtime: &T) -> Option<T::Moment> {
+		let timestamp: &Self = runtime.as_ref();
+		timestamp.now
+	}
+}
+
+impl<T: Config> support::ProvideInherent for Pallet<T> {
+	type Call = Call<T>;
+
+	fn create_inherent(&self, data: &InherentData) -> Option<Self::Call> {
+		data.get_data::<T::Moment>(&INHERENT_IDENTIFIER).map(|now| Call::set { now })
+	}
+
+	fn is_inherent(call: &Self::Call) -> bool {
+		matches!(call, Call::set { .. })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		support::{Dispatch, Extrinsic, InherentData, RawOrigin},
+		timestamp,
+	};
+
+	struct TestConfig;
+
+	impl super::Config for TestConfig {
+		type Moment = u64;
+		const MINIMUM_PERIOD: u64 = 5;
+		const MAXIMUM_DRIFT: u64 = 100;
+	}
+
+	impl crate::system::Config for TestConfig {
+		type AccountId = &'static str;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = ();
+	}
+
+	#[test]
+	fn set_timestamp() {
+		let mut timestamp = super::Pallet::<TestConfig>::new();
+		assert_eq!(timestamp.now(), None);
+		assert_eq!(timestamp.set(RawOrigin::None, 1_000), Ok(()));
+		assert_eq!(timestamp.now(), Some(1_000));
+
+		// Only inherents can set the timestamp.
+		assert!(timestamp.set(RawOrigin::Signed("alice"), 1_010).is_err());
+		assert!(timestamp.set(RawOrigin::Root, 1_010).is_err());
+
+		// The timestamp can not go backwards, or stay too close to the previous one.
+		assert_eq!(
+			timestamp.set(RawOrigin::None, 999),
+			Err("Timestamp must increment by at least the minimum period.")
+		);
+		assert_eq!(
+			timestamp.set(RawOrigin::None, 1_004),
+			Err("Timestamp must increment by at least the minimum period.")
+		);
+		// Nor can it jump too far ahead.
+		assert_eq!(
+			timestamp.set(RawOrigin::None, 1_101),
+			Err("Timestamp is too far ahead of the previous one.")
+		);
+
+		assert_eq!(timestamp.set(RawOrigin::None, 1_100), Ok(()));
+		assert_eq!(timestamp.now(), Some(1_100));
+	}
+
+	#[test]
+	fn zero_timestamp_is_checked() {
+		// A timestamp of zero is a valid previous timestamp, like any other.
+		let mut timestamp = super::Pallet::<TestConfig>::new();
+		assert_eq!(timestamp.set(RawOrigin::None, 0), Ok(()));
+		assert_eq!(timestamp.now(), Some(0));
+		assert_eq!(
+			timestamp.set(RawOrigin::None, 1),
+			Err("Timestamp must increment by at least the minimum period.")
+		);
+		assert_eq!(
+			timestamp.set(RawOrigin::None, 101),
+			Err("Timestamp is too far ahead of the previous one.")
+		);
+	}
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		#[inherent]
+		timestamp: timestamp::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = &'static str;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl timestamp::Config for Runtime {
+		type Moment = u64;
+		const MINIMUM_PERIOD: u64 = 5;
+		const MAXIMUM_DRIFT: u64 = 100;
+	}
+
+	fn inherent_data(now: u64) -> InherentData {
+		let mut data = InherentData::new();
+		data.put_data(super::INHERENT_IDENTIFIER, now);
+		data
+	}
+
+	#[test]
+	fn timestamp_inherent() {
+		let mut runtime = Runtime::new();
+
+		// The block builder places the inherent at the start of the block.
+		let signed = Extrinsic::new_signed(
+			"alice",
+			RuntimeCall::timestamp(timestamp::Call::set { now: 2_000 }),
+		);
+		let block = runtime.build_block(&inherent_data(1_000), vec![signed]);
+		assert_eq!(block.header.block_number, 1);
+		assert_eq!(block.extrinsics.len(), 2);
+		assert!(!block.extrinsics[0].is_signed());
+		assert!(block.extrinsics[1].is_signed());
+
+		// The signed call to the inherent fails, but does not make the block invalid.
+		assert_eq!(runtime.execute_block(block), Ok(()));
+		assert_eq!(runtime.timestamp.now(), Some(1_000));
+		assert_eq!(runtime.system.nonce.get("alice"), Some(&1));
+
+		// Inherents are not charged to anyone, and do not need a signature.
+		let block = runtime.build_block(&inherent_data(1_050), vec![]);
+		assert_eq!(runtime.execute_block(block), Ok(()));
+		assert_eq!(runtime.timestamp.now(), Some(1_050));
+
+		// An invalid timestamp makes the whole block invalid, and none of its changes are kept.
+		let signed = Extrinsic::new_signed(
+			"alice",
+			RuntimeCall::timestamp(timestamp::Call::set { now: 2_000 }),
+		);
+		let block = runtime.build_block(&inherent_data(1_000), vec![signed]);
+		assert!(runtime.execute_block(block).is_err());
+		assert_eq!(runtime.system.block_number(), 2);
+		assert_eq!(runtime.timestamp.now(), Some(1_050));
+		assert_eq!(runtime.system.nonce.get("alice"), Some(&1));
+	}
+
+	#[test]
+	fn inherents_are_checked() {
+		let runtime = Runtime::new();
+		let inherent =
+			|now| Extrinsic::new_inherent(RuntimeCall::timestamp(timestamp::Call::set { now }));
+		let signed = || {
+			Extrinsic::new_signed("alice", RuntimeCall::timestamp(timestamp::Call::set { now: 0 }))
+		};
+
+		// A block without any inherent data is missing the timestamp.
+		let block = runtime.build_block(&InherentData::new(), vec![]);
+		assert_eq!(Runtime::check_inherents(&block), Err("each inherent must appear exactly once"));
+
+		let mut block = runtime.build_block(&inherent_data(1_000), vec![]);
+		assert_eq!(Runtime::check_inherents(&block), Ok(()));
+
+		// The inherent can only appear once.
+		block.extrinsics.push(inherent(1_010));
+		assert_eq!(Runtime::check_inherents(&block), Err("each inherent must appear exactly once"));
+
+		// Inherents must come before any signed extrinsic.
+		block.extrinsics.pop();
+		block.extrinsics.push(signed());
+		assert_eq!(Runtime::check_inherents(&block), Ok(()));
+		block.extrinsics.push(inherent(1_010));
+		assert_eq!(
+			Runtime::check_inherents(&block),
+			Err("inherents must come before all signed extrinsics")
+		);
+
+		// Dispatching the inherent directly needs no origin.
+		let mut runtime = Runtime::new();
+		let call = RuntimeCall::timestamp(timestamp::Call::set { now: 1_000 });
+		assert_eq!(runtime.dispatch(RawOrigin::None, call), Ok(()));
+	}
+}
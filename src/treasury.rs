@@ -0,0 +1,477 @@
+use std::collections::BTreeMap;
+
+use num::{CheckedAdd, CheckedSub, Zero};
+
+use crate::{
+	support::{self, BalanceStatus, Currency, DeriveAccountId, DispatchResult, ReservableCurrency},
+	system::OriginFor,
+};
+
+/// The seed of the account holding the funds of the Treasury Module.
+pub const PALLET_ID: &[u8; 8] = b"py/trsry";
+
+/// The index of a spend proposal.
+pub type ProposalIndex = u32;
+
+/// The type of the funds of the treasury.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config:
+	Sized
+	+ crate::system::Config<AccountId: DeriveAccountId>
+	+ AsRef<Pallet<Self>>
+	+ AsMut<Pallet<Self>>
+{
+	/// The currency the treasury holds and spends.
+	type Currency: ReservableCurrency<Self, Self::AccountId, Balance: Into<u128> + TryFrom<u128>>;
+	/// The number of blocks between two payouts of the approved proposals.
+	const SPEND_PERIOD: Self::BlockNumber;
+	/// The percentage of the funds left unspent at the end of a spend period which is burned.
+	const BURN: u8;
+	/// The bond reserved from the proposer of a spend, which is lost if the proposal is rejected.
+	const PROPOSAL_BOND: BalanceOf<Self>;
+}
+
+/// The events emitted by the Treasury Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// The spend proposal `index` was submitted.
+	Proposed { index: ProposalIndex },
+	/// The spend proposal `index` was approved, and will be paid at the end of a spend period.
+	Approved { index: ProposalIndex },
+	/// The spend proposal `index` was rejected, and its bond of `slashed` was given to the treasury.
+	Rejected { index: ProposalIndex, slashed: BalanceOf<T> },
+	/// A spend period started, with `budget` available to pay the approved proposals.
+	Spending { budget: BalanceOf<T> },
+	/// `award` was paid to `account` for the spend proposal `index`.
+	Awarded { index: ProposalIndex, award: BalanceOf<T>, account: T::AccountId },
+	/// The spend proposal `index` could not be paid, and stays approved.
+	SpendFailed { index: ProposalIndex, error: &'static str },
+	/// `amount` of the funds left unspent was burned.
+	Burnt { amount: BalanceOf<T> },
+	/// `amount` of the funds left unspent could not be burned, and is kept.
+	BurnFailed { amount: BalanceOf<T>, error: &'static str },
+	/// The spend period ended with `remaining` funds left over for the next one.
+	Rollover { remaining: BalanceOf<T> },
+	/// The treasury received `amount`.
+	Deposit { amount: BalanceOf<T> },
+}
+
+/// A proposal to spend some of the funds of the treasury.
+#[derive(Debug, Clone)]
+pub struct SpendProposal<T: Config> {
+	/// The account which proposed the spend, and reserved a bond for it.
+	pub proposer: T::AccountId,
+	/// The amount to pay.
+	pub value: BalanceOf<T>,
+	/// The account to pay.
+	pub beneficiary: T::AccountId,
+	/// The bond reserved from the proposer.
+	pub bond: BalanceOf<T>,
+}
+
+/// This is the Treasury Module.
+/// It holds funds in an account no one has the key of, which receives slashes and other deposits.
+/// Anyone can propose to spend some of them, and the proposals approved by root are paid at the
+/// end of each spend period.
+///
+/// This runtime charges no transaction fees, so the treasury does not receive a share of them: it
+/// is only funded by slashes and other funds given to it through `OnUnbalanced`.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The spend proposals which are not yet rejected or paid.
+	proposals: BTreeMap<ProposalIndex, SpendProposal<T>>,
+	/// The index of the next spend proposal.
+	next_proposal: ProposalIndex,
+	/// The proposals approved by root, in the order they will be paid.
+	approvals: Vec<ProposalIndex>,
+	/// The block at which the next spend period ends.
+	next_spend: T::BlockNumber,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Treasury Module.
+	pub fn new() -> Self {
+		Self {
+			proposals: BTreeMap::new(),
+			next_proposal: 0,
+			approvals: Vec::new(),
+			next_spend: T::SPEND_PERIOD,
+			events: Vec::new(),
+		}
+	}
+
+	/// Get the spend proposal `index`.
+	pub fn proposal(&self, index: ProposalIndex) -> Option<&SpendProposal<T>> {
+		self.proposals.get(&index)
+	}
+
+	/// Get the proposals approved by root, in the order they will be paid.
+	pub fn approvals(&self) -> &[ProposalIndex] {
+		&self.approvals
+	}
+
+	/// The account holding the funds of the treasury.
+	pub fn account_id() -> T::AccountId {
+		T::AccountId::derive_account_id(&support::sha2_256(PALLET_ID))
+	}
+
+	/// The funds of the treasury which are available to spend.
+	pub fn pot(runtime: &T) -> BalanceOf<T> {
+		T::Currency::free_balance(runtime, &Self::account_id())
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// Pay the approved proposals which fit in the funds of the treasury, and burn `BURN` percent
+	/// of the funds left unspent.
+	fn spend_funds(runtime: &mut T) {
+		let budget = Self::pot(runtime);
+		let treasury: &mut Self = runtime.as_mut();
+		treasury.deposit_event(Event::Spending { budget });
+
+		let mut remaining = budget;
+		let approvals = core::mem::take(&mut treasury.approvals);
+		for index in approvals {
+			let treasury: &mut Self = runtime.as_mut();
+			let Some(proposal) = treasury.proposals.get(&index) else { continue };
+			let Some(new_remaining) = remaining.checked_sub(&proposal.value) else {
+				// The proposal does not fit in the budget, so it waits for the next spend period.
+				treasury.approvals.push(index);
+				continue;
+			};
+			let (value, beneficiary) = (proposal.value, proposal.beneficiary.clone());
+
+			let result = T::Currency::transfer(runtime, &Self::account_id(), &beneficiary, value);
+			let treasury: &mut Self = runtime.as_mut();
+			if let Err(error) = result {
+				// The proposal stays approved, and is paid in a later spend period if it can be.
+				treasury.approvals.push(index);
+				treasury.deposit_event(Event::SpendFailed { index, error });
+				continue;
+			}
+			remaining = new_remaining;
+			let proposal = treasury.proposals.remove(&index).expect("The proposal exists.");
+			treasury.deposit_event(Event::Awarded { index, award: value, account: beneficiary });
+			T::Currency::unreserve(runtime, &proposal.proposer, proposal.bond);
+		}
+
+		let burn = remaining.into().saturating_mul(u128::from(T::BURN)) / 100;
+		let burn = BalanceOf::<T>::try_from(burn).unwrap_or_else(|_| BalanceOf::<T>::zero());
+		if !burn.is_zero() {
+			let result = T::Currency::withdraw(runtime, &Self::account_id(), burn);
+			let treasury: &mut Self = runtime.as_mut();
+			match result {
+				Ok(()) => {
+					remaining = remaining.checked_sub(&burn).unwrap_or(remaining);
+					treasury.deposit_event(Event::Burnt { amount: burn });
+				},
+				Err(error) => treasury.deposit_event(Event::BurnFailed { amount: burn, error }),
+			}
+		}
+
+		let treasury: &mut Self = runtime.as_mut();
+		treasury.deposit_event(Event::Rollover { remaining });
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Propose to pay `value` from the treasury to `beneficiary`, reserving `PROPOSAL_BOND` from
+	/// the caller until the proposal is paid or rejected.
+	pub fn propose_spend(
+		runtime: &mut T,
+		caller: T::AccountId,
+		value: BalanceOf<T>,
+		beneficiary: T::AccountId,
+	) -> DispatchResult {
+		T::Currency::reserve(runtime, &caller, T::PROPOSAL_BOND)?;
+
+		let treasury: &mut Self = runtime.as_mut();
+		let index = treasury.next_proposal;
+		treasury.next_proposal += 1;
+		let proposal =
+			SpendProposal { proposer: caller, value, beneficiary, bond: T::PROPOSAL_BOND };
+		treasury.proposals.insert(index, proposal);
+		treasury.deposit_event(Event::Proposed { index });
+		Ok(())
+	}
+
+	/// Reject the spend proposal `proposal`, giving its bond to the treasury. Only root can reject
+	/// a proposal.
+	pub fn reject_proposal(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		proposal: ProposalIndex,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		let treasury: &mut Self = runtime.as_mut();
+		let rejected = treasury.proposals.get(&proposal).ok_or("Proposal does not exist.")?;
+		let (proposer, bond) = (rejected.proposer.clone(), rejected.bond);
+
+		let slashed = T::Currency::repatriate_reserved(
+			runtime,
+			&proposer,
+			&Self::account_id(),
+			bond,
+			BalanceStatus::Free,
+		)?;
+
+		let treasury: &mut Self = runtime.as_mut();
+		treasury.proposals.remove(&proposal);
+		treasury.approvals.retain(|index| *index != proposal);
+		treasury.deposit_event(Event::Rejected { index: proposal, slashed });
+		Ok(())
+	}
+
+	/// Approve the spend proposal `proposal`, to be paid at the end of a spend period. Only root
+	/// can approve a proposal.
+	pub fn approve_proposal(
+		&mut self,
+		origin: OriginFor<T>,
+		proposal: ProposalIndex,
+	) -> DispatchResult {
+		support::ensure_root(origin)?;
+		if !self.proposals.contains_key(&proposal) {
+			return Err("Proposal does not exist.");
+		}
+		if self.approvals.contains(&proposal) {
+			return Err("Proposal is already approved.");
+		}
+		self.approvals.push(proposal);
+		self.deposit_event(Event::Approved { index: proposal });
+		Ok(())
+	}
+}
+
+impl<T: Config> support::Hooks<T, T::BlockNumber> for Pallet<T> {
+	/// Pay the approved proposals at the end of every spend period.
+	fn on_initialize(runtime: &mut T, now: T::BlockNumber) {
+		let treasury: &mut Self = runtime.as_mut();
+		if now >= treasury.next_spend {
+			treasury.next_spend = now.checked_add(&T::SPEND_PERIOD).unwrap_or(now);
+			Self::spend_funds(runtime);
+		}
+	}
+}
+
+/// The treasury receives the funds it is given, like slashes.
+impl<T: Config> support::OnUnbalanced<T, BalanceOf<T>> for Pallet<T> {
+	fn on_unbalanced(runtime: &mut T, amount: BalanceOf<T>) {
+		if T::Currency::deposit_creating(runtime, &Self::account_id(), amount).is_err() {
+			return;
+		}
+
+		let treasury: &mut Self = runtime.as_mut();
+		treasury.deposit_event(Event::Deposit { amount });
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Event;
+	use crate::{
+		balances, staking,
+		support::{Dispatch, RawOrigin},
+		testing::account,
+		treasury,
+	};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[hooks]
+		#[event]
+		staking: staking::Pallet<Self>,
+		#[hooks]
+		#[event]
+		treasury: treasury::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = String;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl staking::Config for Runtime {
+		type Currency = balances::Pallet<Runtime>;
+		type Slash = treasury::Pallet<Runtime>;
+		type RewardRemainder = treasury::Pallet<Runtime>;
+		const BLOCKS_PER_ERA: u32 = 10;
+		const BONDING_DURATION: staking::EraIndex = 2;
+		const ERA_REWARD: u32 = 0;
+		const MIN_BOND: u32 = 10;
+		const MAX_NOMINATIONS: u32 = 2;
+	}
+
+	impl treasury::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		const SPEND_PERIOD: u32 = 3;
+		const BURN: u8 = 50;
+		const PROPOSAL_BOND: u32 = 10;
+	}
+
+	type Treasury = treasury::Pallet<Runtime>;
+
+	crate::testing::impl_run_to_block!();
+
+	crate::testing::impl_dispatch!(treasury, origin);
+
+	fn signed(name: &str) -> RawOrigin<String> {
+		RawOrigin::Signed(account(name))
+	}
+
+	#[test]
+	fn spend_proposals() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+		runtime.balances.set_balance(Treasury::account_id(), 100).unwrap();
+
+		let propose = |value, beneficiary| treasury::Call::propose_spend {
+			value,
+			beneficiary: account(beneficiary),
+		};
+		dispatch(&mut runtime, signed("alice"), propose(60, "bob")).unwrap();
+		dispatch(&mut runtime, signed("alice"), propose(60, "charlie")).unwrap();
+		dispatch(&mut runtime, signed("alice"), propose(10, "dave")).unwrap();
+		assert_eq!(runtime.balances.balance(&account("alice")), 70);
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 30);
+		assert_eq!(Treasury::pot(&runtime), 100);
+
+		// Only root can approve or reject proposals.
+		let approve = |proposal| treasury::Call::approve_proposal { proposal };
+		assert_eq!(
+			dispatch(&mut runtime, signed("alice"), approve(0)),
+			Err("Bad origin: expected the root origin.")
+		);
+		dispatch(&mut runtime, RawOrigin::Root, approve(0)).unwrap();
+		dispatch(&mut runtime, RawOrigin::Root, approve(1)).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, RawOrigin::Root, approve(1)),
+			Err("Proposal is already approved.")
+		);
+		assert_eq!(
+			dispatch(&mut runtime, RawOrigin::Root, approve(3)),
+			Err("Proposal does not exist.")
+		);
+
+		// A rejected proposal loses its bond to the treasury.
+		let reject = treasury::Call::reject_proposal { proposal: 2 };
+		dispatch(&mut runtime, RawOrigin::Root, reject).unwrap();
+		assert!(runtime.treasury.proposal(2).is_none());
+		assert_eq!(Treasury::pot(&runtime), 110);
+
+		// At the end of the spend period, the proposals which fit in the budget are paid, and half
+		// of the funds left are burned.
+		run_to_block(&mut runtime, 3);
+		assert_eq!(runtime.balances.balance(&account("bob")), 60);
+		assert_eq!(runtime.balances.balance(&account("charlie")), 0);
+		assert_eq!(runtime.balances.balance(&account("alice")), 80);
+		assert_eq!(runtime.treasury.approvals(), &[1]);
+		assert_eq!(Treasury::pot(&runtime), 25);
+		assert!(matches!(
+			runtime.system.events(),
+			[
+				RuntimeEvent::treasury(Event::Spending { budget: 110 }),
+				RuntimeEvent::treasury(Event::Awarded { index: 0, award: 60, .. }),
+				RuntimeEvent::treasury(Event::Burnt { amount: 25 }),
+				RuntimeEvent::treasury(Event::Rollover { remaining: 25 }),
+			]
+		));
+	}
+
+	#[test]
+	fn failed_spends_stay_approved() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+		runtime.balances.set_balance(Treasury::account_id(), 100).unwrap();
+		let propose = treasury::Call::propose_spend { value: 60, beneficiary: account("bob") };
+		dispatch(&mut runtime, signed("alice"), propose).unwrap();
+		let approve = treasury::Call::approve_proposal { proposal: 0 };
+		dispatch(&mut runtime, RawOrigin::Root, approve).unwrap();
+
+		// The funds of the treasury cannot be spent while they are locked.
+		let id = *b"testlock";
+		runtime.balances.set_lock(id, &Treasury::account_id(), 100);
+		run_to_block(&mut runtime, 3);
+		assert_eq!(runtime.balances.balance(&account("bob")), 0);
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 10);
+		assert!(runtime.treasury.proposal(0).is_some());
+		assert_eq!(runtime.treasury.approvals(), &[0]);
+		assert!(matches!(
+			runtime.system.events(),
+			[
+				RuntimeEvent::treasury(Event::Spending { budget: 100 }),
+				RuntimeEvent::treasury(Event::SpendFailed {
+					index: 0,
+					error: "Not enough unlocked funds!"
+				}),
+				RuntimeEvent::treasury(Event::BurnFailed { amount: 50, .. }),
+				RuntimeEvent::treasury(Event::Rollover { remaining: 100 }),
+			]
+		));
+
+		// The proposal is paid in the next spend period.
+		runtime.balances.remove_lock(id, &Treasury::account_id());
+		run_to_block(&mut runtime, 6);
+		assert_eq!(runtime.balances.balance(&account("bob")), 60);
+		assert_eq!(runtime.balances.reserved_balance(&account("alice")), 0);
+		assert!(runtime.treasury.proposal(0).is_none());
+		assert!(runtime.treasury.approvals().is_empty());
+	}
+
+	#[test]
+	fn slashes_go_to_the_treasury() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 100).unwrap();
+		let bond = RuntimeCall::staking(staking::Call::bond { value: 50 });
+		runtime.dispatch(signed("alice"), bond).unwrap();
+
+		let slash =
+			|amount| RuntimeCall::staking(staking::Call::slash { stash: account("alice"), amount });
+		assert_eq!(
+			runtime.dispatch(signed("bob"), slash(20)),
+			Err("Bad origin: expected the root origin.")
+		);
+		runtime.dispatch(RawOrigin::Root, slash(20)).unwrap();
+		assert_eq!(runtime.staking.ledger(&account("alice")).unwrap().active, 30);
+		assert_eq!(runtime.balances.balance(&account("alice")), 80);
+		assert_eq!(runtime.balances.locked(&account("alice")), 30);
+		assert_eq!(Treasury::pot(&runtime), 20);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::treasury(Event::Deposit { amount: 20 }))
+		));
+
+		// No more than the active funds can be slashed.
+		runtime.dispatch(RawOrigin::Root, slash(100)).unwrap();
+		assert_eq!(runtime.staking.ledger(&account("alice")).unwrap().active, 0);
+		assert_eq!(Treasury::pot(&runtime), 50);
+		// Slashed funds are burned, and minted again into the treasury.
+		runtime.balances.check_issuance().unwrap();
+	}
+}
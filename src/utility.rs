@@ -0,0 +1,240 @@
+use crate::{
+	support::{self, CallOf, Dispatch, DispatchResult},
+	system::OriginFor,
+};
+
+pub trait Config:
+	Sized + Clone + crate::system::Config + Dispatch<Caller = OriginFor<Self>> + AsMut<Pallet<Self>>
+{
+}
+
+/// The events emitted by the Utility Module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+	/// A `batch` was interrupted by the call at `index` failing with `error`.
+	BatchInterrupted { index: u32, error: &'static str },
+	/// All the calls of a batch were dispatched successfully.
+	BatchCompleted,
+	/// All the calls of a `force_batch` were dispatched, but some of them failed.
+	BatchCompletedWithErrors,
+	/// A call of a `force_batch` was dispatched successfully.
+	ItemCompleted,
+	/// A call of a `force_batch` failed with `error`.
+	ItemFailed { error: &'static str },
+}
+
+/// This is the Utility Module.
+/// It allows a caller to dispatch many calls with a single extrinsic.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event>,
+	_config: core::marker::PhantomData<T>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Utility Module.
+	pub fn new() -> Self {
+		Self { events: Vec::new(), _config: core::marker::PhantomData }
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event) {
+		self.events.push(event);
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Dispatch each of the `calls` on behalf of `origin`, stopping at the first one which fails.
+	/// The calls dispatched before the failure are not reverted, and the batch itself succeeds with
+	/// a `BatchInterrupted` event.
+	pub fn batch(runtime: &mut T, origin: OriginFor<T>, calls: Vec<CallOf<T>>) -> DispatchResult {
+		for (index, call) in calls.into_iter().enumerate() {
+			if let Err(error) = runtime.dispatch(origin.clone(), call) {
+				let utility: &mut Self = runtime.as_mut();
+				utility.deposit_event(Event::BatchInterrupted { index: index as u32, error });
+				return Ok(());
+			}
+		}
+		let utility: &mut Self = runtime.as_mut();
+		utility.deposit_event(Event::BatchCompleted);
+		Ok(())
+	}
+
+	/// Dispatch each of the `calls` on behalf of `origin` atomically: if any of them fails, all
+	/// of them are reverted, and the batch fails with the same error.
+	pub fn batch_all(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		calls: Vec<CallOf<T>>,
+	) -> DispatchResult {
+		support::with_transaction(runtime, |runtime| {
+			for call in calls {
+				runtime.dispatch(origin.clone(), call)?;
+			}
+			Ok(())
+		})?;
+		let utility: &mut Self = runtime.as_mut();
+		utility.deposit_event(Event::BatchCompleted);
+		Ok(())
+	}
+
+	/// Dispatch each of the `calls` on behalf of `origin`, continuing past the ones which fail.
+	/// The result of each call is reported with an `ItemCompleted` or `ItemFailed` event.
+	pub fn force_batch(
+		runtime: &mut T,
+		origin: OriginFor<T>,
+		calls: Vec<CallOf<T>>,
+	) -> DispatchResult {
+		let mut has_errors = false;
+		for call in calls {
+			let result = runtime.dispatch(origin.clone(), call);
+			let utility: &mut Self = runtime.as_mut();
+			match result {
+				Ok(()) => utility.deposit_event(Event::ItemCompleted),
+				Err(error) => {
+					has_errors = true;
+					utility.deposit_event(Event::ItemFailed { error });
+				},
+			}
+		}
+		let utility: &mut Self = runtime.as_mut();
+		if has_errors {
+			utility.deposit_event(Event::BatchCompletedWithErrors);
+		} else {
+			utility.deposit_event(Event::BatchCompleted);
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Event;
+	use crate::{
+		balances,
+		support::{Extrinsic, InherentData},
+		utility,
+	};
+
+	#[derive(Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[event]
+		utility: utility::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = &'static str;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl utility::Config for Runtime {}
+
+	fn transfer(to: &'static str, amount: u32) -> RuntimeCall {
+		RuntimeCall::balances(balances::Call::transfer { to, amount })
+	}
+
+	fn execute_call(runtime: &mut Runtime, call: utility::Call<Runtime>) {
+		let extrinsic = Extrinsic::new_signed("alice", RuntimeCall::utility(call));
+		let block = runtime.build_block(&InherentData::new(), vec![extrinsic]);
+		runtime.execute_block(block).unwrap();
+	}
+
+	fn utility_events(runtime: &Runtime) -> Vec<Event> {
+		runtime
+			.system
+			.events()
+			.iter()
+			.map(|event| match event {
+				RuntimeEvent::utility(event) => event.clone(),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn batch() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+
+		let calls = vec![transfer("bob", 10), transfer("charlie", 20)];
+		execute_call(&mut runtime, utility::Call::batch { calls });
+		assert_eq!(runtime.balances.balance(&"bob"), 10);
+		assert_eq!(runtime.balances.balance(&"charlie"), 20);
+		assert_eq!(utility_events(&runtime), vec![Event::BatchCompleted]);
+		// The whole batch only counts as a single transaction.
+		assert_eq!(runtime.system.nonce.get("alice"), Some(&1));
+
+		// The batch stops at the first failure, but keeps the calls before it.
+		let calls = vec![transfer("bob", 10), transfer("bob", 1_000), transfer("charlie", 20)];
+		execute_call(&mut runtime, utility::Call::batch { calls });
+		assert_eq!(runtime.balances.balance(&"bob"), 20);
+		assert_eq!(runtime.balances.balance(&"charlie"), 20);
+		assert_eq!(
+			utility_events(&runtime),
+			vec![Event::BatchInterrupted { index: 1, error: "Not enough funds!" }]
+		);
+	}
+
+	#[test]
+	fn batch_all() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+
+		let calls = vec![transfer("bob", 10), transfer("charlie", 20)];
+		execute_call(&mut runtime, utility::Call::batch_all { calls });
+		assert_eq!(runtime.balances.balance(&"alice"), 70);
+		assert_eq!(utility_events(&runtime), vec![Event::BatchCompleted]);
+
+		// All the calls are reverted when one of them fails.
+		let calls = vec![transfer("bob", 10), transfer("bob", 1_000), transfer("charlie", 20)];
+		let call = RuntimeCall::utility(utility::Call::batch_all { calls });
+		let origin = crate::support::RawOrigin::Signed("alice");
+		assert_eq!(
+			crate::support::Dispatch::dispatch(&mut runtime, origin, call),
+			Err("Not enough funds!")
+		);
+		assert_eq!(runtime.balances.balance(&"alice"), 70);
+		assert_eq!(runtime.balances.balance(&"bob"), 10);
+		assert_eq!(runtime.balances.balance(&"charlie"), 20);
+	}
+
+	#[test]
+	fn force_batch() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance("alice", 100).unwrap();
+
+		let calls = vec![transfer("bob", 10), transfer("bob", 1_000), transfer("charlie", 20)];
+		execute_call(&mut runtime, utility::Call::force_batch { calls });
+		assert_eq!(runtime.balances.balance(&"bob"), 10);
+		assert_eq!(runtime.balances.balance(&"charlie"), 20);
+		assert_eq!(
+			utility_events(&runtime),
+			vec![
+				Event::ItemCompleted,
+				Event::ItemFailed { error: "Not enough funds!" },
+				Event::ItemCompleted,
+				Event::BatchCompletedWithErrors,
+			]
+		);
+	}
+}
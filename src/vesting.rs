@@ -0,0 +1,336 @@
+use std::collections::BTreeMap;
+
+use num::{CheckedAdd, Zero};
+
+use crate::support::{self, Currency, DispatchResult, LockIdentifier, LockableCurrency};
+
+/// The identifier of the lock the Vesting Module puts on the vesting funds of an account.
+pub const VESTING_ID: LockIdentifier = *b"vesting ";
+
+/// The type of the vesting funds.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<T, <T as crate::system::Config>::AccountId>>::Balance;
+
+pub trait Config:
+	Sized
+	+ crate::system::Config<BlockNumber: Into<u128>>
+	+ AsRef<crate::system::Pallet<Self>>
+	+ AsRef<Pallet<Self>>
+	+ AsMut<Pallet<Self>>
+{
+	/// The currency the vesting funds are locked in.
+	type Currency: LockableCurrency<Self, Self::AccountId, Balance: Into<u128> + TryFrom<u128>>;
+	/// The minimum amount which can be transferred with `vested_transfer`.
+	const MIN_VESTED_TRANSFER: BalanceOf<Self>;
+	/// The maximum number of vesting schedules of an account.
+	const MAX_VESTING_SCHEDULES: u32;
+}
+
+/// The events emitted by the Vesting Module.
+#[derive(Debug, Clone)]
+pub enum Event<T: Config> {
+	/// The lock on the funds of `account` was updated, and `unvested` of them are still locked.
+	VestingUpdated { account: T::AccountId, unvested: BalanceOf<T> },
+	/// All the funds of `account` are vested, and its lock was removed.
+	VestingCompleted { account: T::AccountId },
+}
+
+/// A vesting schedule, unlocking `per_block` of `locked` funds at each block from
+/// `starting_block`. If there is a `cliff`, nothing unlocks before it, and the funds which would
+/// have unlocked before it all unlock at once when it is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VestingInfo<Balance, BlockNumber> {
+	/// The funds locked at the start of the schedule.
+	pub locked: Balance,
+	/// The funds which unlock at each block.
+	pub per_block: Balance,
+	/// The block at which the funds start to unlock.
+	pub starting_block: BlockNumber,
+	/// The block before which no funds unlock.
+	pub cliff: Option<BlockNumber>,
+}
+
+impl<Balance, BlockNumber> VestingInfo<Balance, BlockNumber>
+where
+	Balance: Zero + Copy + Into<u128> + TryFrom<u128>,
+	BlockNumber: Copy + Ord + Into<u128>,
+{
+	/// Check that the schedule locks some funds, and unlocks them eventually.
+	pub fn validate(&self) -> DispatchResult {
+		if self.locked.is_zero() || self.per_block.is_zero() {
+			return Err("Invalid vesting schedule.");
+		}
+		Ok(())
+	}
+
+	/// The funds of the schedule which are still locked at the block `now`.
+	pub fn locked_at(&self, now: BlockNumber) -> Balance {
+		if now < self.starting_block || self.cliff.is_some_and(|cliff| now < cliff) {
+			return self.locked;
+		}
+		let elapsed = now.into() - self.starting_block.into();
+		let vested = self.per_block.into().saturating_mul(elapsed);
+		let locked = self.locked.into().saturating_sub(vested);
+		Balance::try_from(locked).unwrap_or(self.locked)
+	}
+}
+
+/// The vesting schedule of the configured runtime.
+pub type VestingInfoOf<T> = VestingInfo<BalanceOf<T>, <T as crate::system::Config>::BlockNumber>;
+
+/// This is the Vesting Module.
+/// It locks funds granted to an account, and unlocks them a little at each block following the
+/// vesting schedules of the account. The lock is only updated when the account vests.
+#[derive(Debug, Clone)]
+pub struct Pallet<T: Config> {
+	/// The vesting schedules of each account, which are not done unlocking.
+	vesting: BTreeMap<T::AccountId, Vec<VestingInfoOf<T>>>,
+	/// The events emitted by this pallet, waiting to be collected by the runtime.
+	events: Vec<Event<T>>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Create a new instance of the Vesting Module.
+	pub fn new() -> Self {
+		Self { vesting: BTreeMap::new(), events: Vec::new() }
+	}
+
+	/// Get the vesting schedules of `who`.
+	pub fn vesting(&self, who: &T::AccountId) -> &[VestingInfoOf<T>] {
+		self.vesting.get(who).map(Vec::as_slice).unwrap_or_default()
+	}
+
+	/// Get the funds of `who` which are not vested yet at the current block.
+	pub fn vesting_balance(runtime: &T, who: &T::AccountId) -> BalanceOf<T> {
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		let now = system.block_number();
+		let vesting: &Self = runtime.as_ref();
+		vesting.vesting(who).iter().fold(BalanceOf::<T>::zero(), |total, schedule| {
+			total.checked_add(&schedule.locked_at(now)).unwrap_or(total)
+		})
+	}
+
+	/// Add a vesting `schedule` to the funds `who` already has, and lock them. This is how funds
+	/// are granted at genesis.
+	pub fn add_vesting_schedule(
+		runtime: &mut T,
+		who: &T::AccountId,
+		schedule: VestingInfoOf<T>,
+	) -> DispatchResult {
+		schedule.validate()?;
+		let vesting: &mut Self = runtime.as_mut();
+		let schedules = vesting.vesting.entry(who.clone()).or_default();
+		if schedules.len() >= T::MAX_VESTING_SCHEDULES as usize {
+			return Err("Too many vesting schedules.");
+		}
+		schedules.push(schedule);
+		Self::update_lock(runtime, who);
+		Ok(())
+	}
+
+	/// Emit an `event` from this pallet.
+	fn deposit_event(&mut self, event: Event<T>) {
+		self.events.push(event);
+	}
+
+	/// Lock the funds of `who` which are not vested yet, and forget the schedules which are done.
+	fn update_lock(runtime: &mut T, who: &T::AccountId) {
+		let unvested = Self::vesting_balance(runtime, who);
+		T::Currency::set_lock(runtime, VESTING_ID, who, unvested);
+
+		let system: &crate::system::Pallet<T> = runtime.as_ref();
+		let now = system.block_number();
+		let vesting: &mut Self = runtime.as_mut();
+		if let Some(schedules) = vesting.vesting.get_mut(who) {
+			schedules.retain(|schedule| !schedule.locked_at(now).is_zero());
+			if schedules.is_empty() {
+				vesting.vesting.remove(who);
+			}
+		}
+
+		let event = if unvested.is_zero() {
+			Event::VestingCompleted { account: who.clone() }
+		} else {
+			Event::VestingUpdated { account: who.clone(), unvested }
+		};
+		vesting.deposit_event(event);
+	}
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+	/// Unlock the funds of the caller which are vested.
+	pub fn vest(runtime: &mut T, caller: T::AccountId) -> DispatchResult {
+		let vesting: &Self = runtime.as_ref();
+		if !vesting.vesting.contains_key(&caller) {
+			return Err("Account is not vesting.");
+		}
+		Self::update_lock(runtime, &caller);
+		Ok(())
+	}
+
+	/// Unlock the funds of `target` which are vested, on its behalf.
+	pub fn vest_other(
+		runtime: &mut T,
+		_caller: T::AccountId,
+		target: T::AccountId,
+	) -> DispatchResult {
+		Self::vest(runtime, target)
+	}
+
+	/// Transfer `schedule.locked` from the caller to `target`, locking them following `schedule`.
+	pub fn vested_transfer(
+		runtime: &mut T,
+		caller: T::AccountId,
+		target: T::AccountId,
+		schedule: VestingInfoOf<T>,
+	) -> DispatchResult {
+		if schedule.locked < T::MIN_VESTED_TRANSFER {
+			return Err("Amount is below the minimum vested transfer.");
+		}
+		schedule.validate()?;
+		let vesting: &Self = runtime.as_ref();
+		if vesting.vesting(&target).len() >= T::MAX_VESTING_SCHEDULES as usize {
+			return Err("Too many vesting schedules.");
+		}
+
+		T::Currency::transfer(runtime, &caller, &target, schedule.locked)?;
+		Self::add_vesting_schedule(runtime, &target, schedule)
+	}
+}
+
+impl<T: Config> support::PendingEvents for Pallet<T> {
+	type Event = Event<T>;
+
+	fn take_events(&mut self) -> Vec<Self::Event> {
+		core::mem::take(&mut self.events)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Event, VestingInfo};
+	use crate::{balances, support, testing::account, vesting};
+
+	#[derive(Debug, Clone)]
+	#[macros::runtime]
+	struct Runtime {
+		system: crate::system::Pallet<Self>,
+		balances: balances::Pallet<Self>,
+		#[event]
+		vesting: vesting::Pallet<Self>,
+	}
+
+	impl crate::system::Config for Runtime {
+		type AccountId = String;
+		type BlockNumber = u32;
+		type Nonce = u32;
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl balances::Config for Runtime {
+		type Balance = u32;
+		type DustRemoval = ();
+		const EXISTENTIAL_DEPOSIT: u32 = 1;
+	}
+
+	impl vesting::Config for Runtime {
+		type Currency = balances::Pallet<Self>;
+		const MIN_VESTED_TRANSFER: u32 = 10;
+		const MAX_VESTING_SCHEDULES: u32 = 2;
+	}
+
+	type Vesting = vesting::Pallet<Runtime>;
+
+	crate::testing::impl_run_to_block!();
+
+	crate::testing::impl_dispatch!(vesting);
+
+	fn transfer(
+		runtime: &mut Runtime,
+		caller: &str,
+		to: &str,
+		amount: u32,
+	) -> support::DispatchResult {
+		runtime.balances.transfer(account(caller), account(to), amount)
+	}
+
+	#[test]
+	fn vested_transfer_unlocks_linearly() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 1_000).unwrap();
+
+		let schedule =
+			|locked| VestingInfo { locked, per_block: 10, starting_block: 2, cliff: None };
+		let vested_transfer =
+			|schedule| vesting::Call::vested_transfer { target: account("bob"), schedule };
+		assert_eq!(
+			dispatch(&mut runtime, "alice", vested_transfer(schedule(5))),
+			Err("Amount is below the minimum vested transfer.")
+		);
+		dispatch(&mut runtime, "alice", vested_transfer(schedule(100))).unwrap();
+		assert_eq!(runtime.balances.balance(&account("bob")), 100);
+		assert_eq!(runtime.balances.locked(&account("bob")), 100);
+		assert_eq!(transfer(&mut runtime, "bob", "charlie", 1), Err("Not enough unlocked funds!"));
+
+		// The lock only follows the schedule when the account vests.
+		run_to_block(&mut runtime, 5);
+		assert_eq!(Vesting::vesting_balance(&runtime, &account("bob")), 70);
+		assert_eq!(runtime.balances.locked(&account("bob")), 100);
+		dispatch(&mut runtime, "bob", vesting::Call::vest {}).unwrap();
+		assert_eq!(runtime.balances.locked(&account("bob")), 70);
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::vesting(Event::VestingUpdated { unvested: 70, .. }))
+		));
+		assert_eq!(transfer(&mut runtime, "bob", "charlie", 31), Err("Not enough unlocked funds!"));
+		transfer(&mut runtime, "bob", "charlie", 30).unwrap();
+
+		// An account can't have more than `MAX_VESTING_SCHEDULES` schedules.
+		dispatch(&mut runtime, "alice", vested_transfer(schedule(50))).unwrap();
+		assert_eq!(
+			dispatch(&mut runtime, "alice", vested_transfer(schedule(50))),
+			Err("Too many vesting schedules.")
+		);
+		// The new schedule started at block 2 too, so 30 of it are already vested.
+		assert_eq!(runtime.balances.locked(&account("bob")), 90);
+
+		// Once all the funds are vested, the lock and the schedules are removed.
+		run_to_block(&mut runtime, 12);
+		dispatch(&mut runtime, "bob", vesting::Call::vest {}).unwrap();
+		assert_eq!(runtime.balances.locked(&account("bob")), 0);
+		assert!(runtime.vesting.vesting(&account("bob")).is_empty());
+		assert!(matches!(
+			runtime.system.events().last(),
+			Some(RuntimeEvent::vesting(Event::VestingCompleted { .. }))
+		));
+		assert_eq!(
+			dispatch(&mut runtime, "bob", vesting::Call::vest {}),
+			Err("Account is not vesting.")
+		);
+	}
+
+	#[test]
+	fn genesis_schedule_with_cliff() {
+		let mut runtime = Runtime::new();
+		runtime.balances.set_balance(account("alice"), 150).unwrap();
+		let schedule =
+			VestingInfo { locked: 100, per_block: 5, starting_block: 0, cliff: Some(10) };
+		Vesting::add_vesting_schedule(&mut runtime, &account("alice"), schedule).unwrap();
+		assert_eq!(runtime.balances.locked(&account("alice")), 100);
+		transfer(&mut runtime, "alice", "bob", 50).unwrap();
+
+		// Nothing unlocks before the cliff.
+		run_to_block(&mut runtime, 9);
+		let vest_other = vesting::Call::vest_other { target: account("alice") };
+		dispatch(&mut runtime, "bob", vest_other.clone()).unwrap();
+		assert_eq!(runtime.balances.locked(&account("alice")), 100);
+
+		// At the cliff, what vested since the starting block unlocks at once.
+		run_to_block(&mut runtime, 10);
+		dispatch(&mut runtime, "bob", vest_other).unwrap();
+		assert_eq!(runtime.balances.locked(&account("alice")), 50);
+		transfer(&mut runtime, "alice", "bob", 50).unwrap();
+		assert_eq!(transfer(&mut runtime, "alice", "bob", 1), Err("Not enough unlocked funds!"));
+	}
+}